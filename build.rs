@@ -0,0 +1,52 @@
+use std::env;
+use std::fs::{read_dir, write};
+use std::path::Path;
+
+// Finds the day modules in src/days (named <year>/dayNN.rs, each defining a `DayNN` solver), and generates their
+// module declarations and the list of solvers that src/days.rs includes. The modules of a year end up in `yYYYY`.
+fn main() {
+    let days_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("days");
+    println!("cargo:rerun-if-changed={}", days_dir.display());
+
+    let mut years: Vec<i32> = read_dir(&days_dir).expect("Could not read src/days")
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok()?.parse().ok())
+        .collect();
+    years.sort();
+
+    let mut modules = vec![];
+    let mut solvers = vec![];
+    for year in years {
+        let year_dir = days_dir.join(year.to_string());
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        let mut days: Vec<i32> = read_dir(&year_dir).expect("Could not read year folder")
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|name| {
+                let day: i32 = name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()?;
+                // The module is declared as dayNN, so the file has to be named like that too.
+                if name != format!("day{:02}.rs", day) {
+                    panic!("src/days/{}/{} should be named day{:02}.rs", year, name, day);
+                }
+                Some(day)
+            })
+            .collect();
+        days.sort();
+
+        modules.push(format!("mod y{} {{", year));
+        for day in days {
+            modules.push(format!("    #[path = {:?}]", year_dir.join(format!("day{:02}.rs", day)).display().to_string()));
+            modules.push(format!("    pub(crate) mod day{:02};", day));
+            solvers.push(format!("    ({year}, {day}, Day::of::<y{year}::day{day:02}::Day{day:02}>()),", year = year, day = day));
+        }
+        modules.push("}".to_string());
+    }
+
+    let mut lines = modules;
+    lines.push(String::new());
+    lines.push("const DAYS: &[(i32, i32, Day)] = &[".to_string());
+    lines.extend(solvers);
+    lines.push("];".to_string());
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+    write(Path::new(&out_dir).join("days.rs"), lines.join("\n")).expect("Could not write days.rs");
+}
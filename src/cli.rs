@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+pub struct Command {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: &'static [Arg],
+    pub options: &'static [Opt],
+}
+
+pub struct Arg {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+}
+
+pub struct Opt {
+    pub name: &'static str,
+    // Name of the value this option takes, or None for a simple on/off flag.
+    pub value: Option<&'static str>,
+    pub description: &'static str,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct Matches {
+    pub command: &'static str,
+    args: HashMap<&'static str, String>,
+    options: HashMap<&'static str, String>,
+}
+
+impl Matches {
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args.get(name).map(|v| v.as_str())
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|v| v.as_str())
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+}
+
+pub enum Parsed {
+    Run(Matches),
+    Help(Option<&'static Command>),
+}
+
+// Parses the given arguments (excluding the program name) against the given commands.
+pub fn parse(commands: &'static [Command], args: &[String]) -> Result<Parsed, String> {
+    let name = match args.first().map(|a| a.as_str()) {
+        None | Some("help") | Some("--help") | Some("-h") => {
+            return Ok(Parsed::Help(args.get(1).and_then(|n| commands.iter().find(|c| c.name == n))));
+        }
+        Some(name) => name,
+    };
+    let command = commands.iter().find(|c| c.name == name).ok_or_else(|| format!("Unknown command '{}'", name))?;
+
+    let mut positional = vec![];
+    let mut options = HashMap::new();
+    let mut remaining = args[1..].iter();
+    while let Some(arg) = remaining.next() {
+        if arg == "--help" || arg == "-h" {
+            return Ok(Parsed::Help(Some(command)));
+        }
+
+        let (option_name, inline_value) = match arg.strip_prefix("--") {
+            Some(option) => match option.split_once('=') {
+                Some((n, v)) => (n, Some(v.to_string())),
+                None => (option, None)
+            },
+            None => {
+                positional.push(arg.clone());
+                continue;
+            }
+        };
+
+        let option = command.options.iter().find(|o| o.name == option_name)
+            .ok_or_else(|| format!("Unknown option '--{}' for command '{}'", option_name, command.name))?;
+        let value = match (option.value, inline_value) {
+            (Some(_), Some(v)) => v,
+            (Some(value_name), None) => remaining.next().cloned()
+                .ok_or_else(|| format!("Option '--{}' requires a <{}>", option.name, value_name))?,
+            (None, Some(_)) => return Err(format!("Option '--{}' does not take a value", option.name)),
+            (None, None) => String::new(),
+        };
+        options.insert(option.name, value);
+    }
+
+    if positional.len() > command.args.len() {
+        return Err(format!("Too many arguments for command '{}'", command.name));
+    }
+    let mut matched_args = HashMap::new();
+    for (i, arg) in command.args.iter().enumerate() {
+        match positional.get(i) {
+            Some(value) => { matched_args.insert(arg.name, value.clone()); }
+            None if arg.required => return Err(format!("Missing required argument <{}> for command '{}'", arg.name, command.name)),
+            None => {}
+        }
+    }
+
+    Ok(Parsed::Run(Matches { command: command.name, args: matched_args, options }))
+}
+
+fn command_synopsis(command: &Command) -> String {
+    let mut parts = vec![command.name.to_string()];
+    for arg in command.args {
+        parts.push(if arg.required { format!("<{}>", arg.name) } else { format!("[<{}>]", arg.name) });
+    }
+    if !command.options.is_empty() {
+        parts.push("[options]".to_string());
+    }
+    parts.join(" ")
+}
+
+pub fn usage(commands: &[Command]) -> String {
+    let synopses: Vec<_> = commands.iter().map(|c| (command_synopsis(c), c.description)).collect();
+    let width = synopses.iter().map(|(s, _)| s.len()).max().unwrap_or(0);
+
+    let mut lines = vec![
+        "Usage: cargo run <command> [<command_arg>, ...] [options]".to_string(),
+        String::new(),
+        "Commands:".to_string(),
+    ];
+    for (synopsis, description) in synopses {
+        lines.push(format!("    {:<width$}  {}", synopsis, description, width = width));
+    }
+    lines.push(String::new());
+    lines.push("Use 'cargo run help <command>' or '<command> --help' for the options of a command.".to_string());
+    lines.join("\n")
+}
+
+pub fn command_usage(command: &Command) -> String {
+    let mut lines = vec![
+        format!("Usage: cargo run {}", command_synopsis(command)),
+        String::new(),
+        command.description.to_string(),
+    ];
+
+    let mut entries: Vec<(String, &str)> = command.args.iter().map(|a| (format!("<{}>", a.name), a.description)).collect();
+    entries.extend(command.options.iter().map(|o| match o.value {
+        Some(value) => (format!("--{} <{}>", o.name, value), o.description),
+        None => (format!("--{}", o.name), o.description)
+    }));
+    let width = entries.iter().map(|(e, _)| e.len()).max().unwrap_or(0);
+
+    if !command.args.is_empty() {
+        lines.push(String::new());
+        lines.push("Arguments:".to_string());
+        for (entry, description) in &entries[..command.args.len()] {
+            lines.push(format!("    {:<width$}  {}", entry, description, width = width));
+        }
+    }
+    if !command.options.is_empty() {
+        lines.push(String::new());
+        lines.push("Options:".to_string());
+        for (entry, description) in &entries[command.args.len()..] {
+            lines.push(format!("    {:<width$}  {}", entry, description, width = width));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Arg, Command, command_usage, Opt, parse, Parsed, usage};
+
+    const COMMANDS: &[Command] = &[
+        Command {
+            name: "day",
+            description: "Run a day.",
+            args: &[Arg { name: "day", description: "The day to run.", required: true }],
+            options: &[
+                Opt { name: "part", value: Some("part"), description: "Only run the given part." },
+                Opt { name: "quiet", value: None, description: "Do not print." },
+            ],
+        },
+        Command {
+            name: "verify",
+            description: "Verify answers.",
+            args: &[Arg { name: "day", description: "The day to verify.", required: false }],
+            options: &[],
+        },
+    ];
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    fn run(input: &str) -> Result<crate::cli::Matches, String> {
+        match parse(COMMANDS, &args(input))? {
+            Parsed::Run(matches) => Ok(matches),
+            Parsed::Help(_) => Err("help".to_string())
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let matches = run("day 15 --part 2 --quiet").unwrap();
+        assert_eq!(matches.command, "day");
+        assert_eq!(matches.arg("day"), Some("15"));
+        assert_eq!(matches.option("part"), Some("2"));
+        assert!(matches.flag("quiet"));
+
+        let matches = run("day --part=1 3").unwrap();
+        assert_eq!(matches.arg("day"), Some("3"));
+        assert_eq!(matches.option("part"), Some("1"));
+        assert!(!matches.flag("quiet"));
+
+        assert_eq!(run("verify").unwrap().arg("day"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(run("dya 15").is_err());
+        assert!(run("day").is_err());
+        assert!(run("day 1 2").is_err());
+        assert!(run("day 1 --part").is_err());
+        assert!(run("day 1 --quiet=yes").is_err());
+        assert!(run("day 1 --input foo").is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        assert!(matches!(parse(COMMANDS, &args("")), Ok(Parsed::Help(None))));
+        assert!(matches!(parse(COMMANDS, &args("--help")), Ok(Parsed::Help(None))));
+        assert!(matches!(parse(COMMANDS, &args("help day")), Ok(Parsed::Help(Some(c))) if c.name == "day"));
+        assert!(matches!(parse(COMMANDS, &args("verify --help")), Ok(Parsed::Help(Some(c))) if c.name == "verify"));
+    }
+
+    #[test]
+    fn test_usage() {
+        assert_eq!(usage(COMMANDS), "\
+            Usage: cargo run <command> [<command_arg>, ...] [options]\n\
+            \n\
+            Commands:\n    \
+                day <day> [options]  Run a day.\n    \
+                verify [<day>]       Verify answers.\n\
+            \n\
+            Use 'cargo run help <command>' or '<command> --help' for the options of a command.");
+
+        assert_eq!(command_usage(&COMMANDS[0]), "\
+            Usage: cargo run day <day> [options]\n\
+            \n\
+            Run a day.\n\
+            \n\
+            Arguments:\n    \
+                <day>          The day to run.\n\
+            \n\
+            Options:\n    \
+                --part <part>  Only run the given part.\n    \
+                --quiet        Do not print.");
+    }
+}
@@ -1,88 +1,214 @@
-mod day01;
-use day01::DAY1;
-mod day02;
-use day02::DAY2;
-mod day03;
-use day03::DAY3;
-mod day04;
-use day04::DAY4;
-mod day05;
-use day05::DAY5;
-mod day06;
-use day06::DAY6;
-mod day07;
-use day07::DAY7;
-mod day08;
-use day08::DAY8;
-mod day09;
-use day09::DAY9;
-mod day10;
-use day10::DAY10;
-mod day11;
-use day11::DAY11;
-mod day12;
-use day12::DAY12;
-mod day13;
-use day13::DAY13;
-mod day14;
-use day14::DAY14;
-mod day15;
-use day15::DAY15;
-mod day16;
-use day16::DAY16;
-mod day17;
-use day17::DAY17;
-mod day18;
-use day18::DAY18;
-mod day19;
-use day19::DAY19;
-mod day20;
-use day20::DAY20;
-mod day21;
-use day21::DAY21;
-mod day22;
-use day22::DAY22;
-mod day23;
-use day23::DAY23;
-mod day24;
-use day24::DAY24;
-mod day25;
-use day25::DAY25;
-// « add day import »
+use std::any::Any;
+use std::fmt;
+use num_bigint::{BigInt, BigUint};
+use crate::error::Error;
+use crate::util::render::Picture;
 
+// The day modules (src/days/<year>/dayNN.rs) are found by build.rs, which declares them and lists their solvers in DAYS.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+// The solution for a single day. Every src/days/<year>/dayNN.rs defines a `DayNN` implementing this.
+pub trait Solver {
+    // The parsed puzzle input, shared by both parts.
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Answer, Error>;
+    fn part2(input: &Self::Input) -> Result<Answer, Error>;
+
+    // A picture of how the given part was solved, for the render command. Most days don't have one.
+    fn render(_input: &Self::Input, _part: i32) -> Result<Option<Picture>, Error> {
+        Ok(None)
+    }
+}
+
+// The parsed input of a day. Only the puzzles of the day that parsed it know its actual type.
+pub type ParsedInput = Box<dyn Any>;
+pub type Parser = fn(input: &str) -> Result<ParsedInput, Error>;
+pub type Puzzle = fn(input: &ParsedInput) -> Result<Answer, Error>;
+pub type Renderer = fn(input: &ParsedInput, part: i32) -> Result<Option<Picture>, Error>;
+
+#[derive(Copy, Clone)]
 pub struct Day {
-    pub puzzle1: fn(input: &String),
-    pub puzzle2: fn(input: &String)
-}
-
-pub fn get_day(day: i32) -> Result<Day, String> {
-    match day {
-        1 => Ok(DAY1),
-        2 => Ok(DAY2),
-        3 => Ok(DAY3),
-        4 => Ok(DAY4),
-        5 => Ok(DAY5),
-        6 => Ok(DAY6),
-        7 => Ok(DAY7),
-        8 => Ok(DAY8),
-        9 => Ok(DAY9),
-        10 => Ok(DAY10),
-        11 => Ok(DAY11),
-        12 => Ok(DAY12),
-        13 => Ok(DAY13),
-        14 => Ok(DAY14),
-        15 => Ok(DAY15),
-        16 => Ok(DAY16),
-        17 => Ok(DAY17),
-        18 => Ok(DAY18),
-        19 => Ok(DAY19),
-        20 => Ok(DAY20),
-        21 => Ok(DAY21),
-        22 => Ok(DAY22),
-        23 => Ok(DAY23),
-        24 => Ok(DAY24),
-        25 => Ok(DAY25),
-        // « add day match »
-        _ => Err(format!("No implementation yet for day {}", day))
+    pub parse: Parser,
+    pub puzzle1: Puzzle,
+    pub puzzle2: Puzzle,
+    pub render: Renderer,
+}
+
+impl Day {
+    pub const fn of<S: Solver>() -> Self {
+        Day {
+            parse: parse::<S>,
+            puzzle1: solve_part1::<S>,
+            puzzle2: solve_part2::<S>,
+            render: render::<S>,
+        }
+    }
+
+    // The puzzles of this day with their number, limited to the given part if any.
+    pub fn puzzles(&self, part: Option<i32>) -> Vec<(i32, Puzzle)> {
+        [(1, self.puzzle1), (2, self.puzzle2)].into_iter()
+            .filter(|(p, _)| part.is_none_or(|part| part == *p))
+            .collect()
+    }
+}
+
+fn parse<S: Solver>(input: &str) -> Result<ParsedInput, Error> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn parsed_input<S: Solver>(input: &ParsedInput) -> Result<&S::Input, Error> {
+    input.downcast_ref().ok_or_else(|| Error::invalid_state("The input was parsed for another day"))
+}
+
+fn solve_part1<S: Solver>(input: &ParsedInput) -> Result<Answer, Error> {
+    S::part1(parsed_input::<S>(input)?)
+}
+
+fn solve_part2<S: Solver>(input: &ParsedInput) -> Result<Answer, Error> {
+    S::part2(parsed_input::<S>(input)?)
+}
+
+fn render<S: Solver>(input: &ParsedInput, part: i32) -> Result<Option<Picture>, Error> {
+    S::render(parsed_input::<S>(input)?, part)
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Answer {
+    Integer(i128),
+    BigInteger(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Text(text) => text.contains('\n'),
+            _ => false
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{}", v),
+            Answer::BigInteger(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+macro_rules! answer_from_int_impl {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Integer(value as i128)
+            }
+        }
+    )*}
+}
+
+answer_from_int_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 i128 isize }
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(v) => Answer::Integer(v),
+            Err(_) => Answer::BigInteger(value.into())
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::BigInteger(value)
     }
-}
\ No newline at end of file
+}
+
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Self {
+        Answer::BigInteger(value.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+pub fn get_day(year: i32, day: i32) -> Result<Day, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::InvalidDay(day));
+    }
+
+    DAYS.iter()
+        .find(|(y, d, _)| *y == year && *d == day)
+        .map(|(_, _, solver)| *solver)
+        .ok_or(Error::NotImplemented { year, day })
+}
+
+// The years with at least one implemented day, in order.
+pub fn available_years() -> Vec<i32> {
+    let mut years: Vec<i32> = DAYS.iter().map(|(year, _, _)| *year).collect();
+    years.dedup();
+    years
+}
+
+// The numbers of all days of the given year with an implementation, in order.
+pub fn available_days(year: i32) -> Vec<i32> {
+    DAYS.iter().filter(|(y, _, _)| *y == year).map(|(_, day, _)| *day).collect()
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use num_bigint::BigUint;
+    use crate::days::Answer;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(42usize), Answer::Integer(42));
+        assert_eq!(Answer::from(-3i32), Answer::Integer(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::BigInteger(u128::MAX.into()));
+        assert_eq!(Answer::from(BigUint::from(12u8)), Answer::BigInteger(12.into()));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format!("{}", Answer::Integer(-12)), "-12");
+        assert_eq!(format!("{}", Answer::Text("#.\n.#".to_string())), "#.\n.#");
+        assert!(Answer::Text("#.\n.#".to_string()).is_multiline());
+        assert!(!Answer::Integer(1).is_multiline());
+    }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use crate::days::{Answer, available_days, available_years, get_day};
+    use crate::error::Error;
+
+    #[test]
+    fn test_available_days() {
+        // Other years may have been added with `add` since.
+        assert!(available_years().contains(&2021));
+        assert_eq!(available_days(2021), (1..=25).collect::<Vec<_>>());
+        assert_eq!(available_days(2015), Vec::<i32>::new());
+        assert!(get_day(2021, 15).is_ok());
+        assert_eq!(get_day(2021, 26).err(), Some(Error::InvalidDay(26)));
+        assert_eq!(get_day(2021, 0).err(), Some(Error::InvalidDay(0)));
+        assert_eq!(get_day(2020, 15).err(), Some(Error::NotImplemented { year: 2020, day: 15 }));
+    }
+
+    #[test]
+    fn test_parse_once() {
+        let day1 = get_day(2021, 1).unwrap();
+        let parsed = (day1.parse)("199\n200\n208\n210").unwrap();
+        assert_eq!((day1.puzzle1)(&parsed), Ok(Answer::Integer(3)));
+        assert_eq!((day1.puzzle2)(&parsed), Ok(Answer::Integer(1)));
+
+        let day9 = get_day(2021, 9).unwrap();
+        assert_eq!((day9.puzzle1)(&parsed), Err(Error::invalid_state("The input was parsed for another day")));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::number;
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Vec<u128>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.lines().enumerate().map(|(i, l)| number::parse_u128(l).map_err(|e| e.at_line(i + 1))).collect()
+    }
+
+    fn part1(depths: &Self::Input) -> Result<Answer, Error> {
+        // Puzzle 1
+        // The first order of business is to figure out how quickly the depth increases,
+        // just so you know what you're dealing with - you never know if the keys will
+        // get carried into deeper water by an ocean current or a fish or something.
+        //
+        // To do this, count the number of times a depth measurement increases from the
+        // previous measurement. (There is no measurement before the first measurement.)
+
+        if depths.is_empty() {
+            return Err(Error::no_solution("There are no depth measurements"));
+        }
+
+        let mut increases = 0;
+        let mut last_depth = &depths[0];
+        for depth in &depths[1..] {
+            if depth > last_depth {
+                increases += 1;
+            }
+            last_depth = depth;
+        }
+
+        Ok(increases.into())
+    }
+
+    fn part2(depths: &Self::Input) -> Result<Answer, Error> {
+        // Same as above, but using a sliding window summing three values
+        // A = 0,1,2
+        // B = 1,2,3
+        // ...
+        // Z = N-3, N-2, N-1 (// for N values)
+        if depths.len() < 3 {
+            return Err(Error::no_solution(format!("Expected at least 3 depth measurements, but got {}", depths.len())));
+        }
+
+        let mut last_window: u128 = depths[0..3].iter().sum();
+        let mut increases = 0;
+        for i in 1..depths.len() - 2 {
+            let window = depths[i..i + 3].iter().sum();
+            if window > last_window { increases += 1; }
+            last_window = window;
+        }
+
+        Ok(increases.into())
+    }
+}
+
@@ -0,0 +1,87 @@
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::number;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        to_instructions(input)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Answer, Error> {
+        let mut depth = 0;
+        let mut distance = 0;
+
+        for instruction in instructions {
+            match instruction.command {
+                Command::FORWARD => { distance += instruction.value }
+                Command::UP => { depth -= instruction.value }
+                Command::DOWN => { depth += instruction.value }
+            }
+        }
+
+        let result = depth * distance;
+        Ok(result.into())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Answer, Error> {
+        let mut aim: i32 = 0;
+        let mut depth: i128 = 0;
+        let mut distance: u128 = 0;
+
+        for instruction in instructions {
+            match instruction.command {
+                Command::FORWARD => { distance += instruction.value as u128; depth += (aim * instruction.value) as i128 }
+                Command::UP => { aim -= instruction.value }
+                Command::DOWN => { aim += instruction.value }
+            }
+        }
+
+        let result = depth * distance as i128;
+        Ok(result.into())
+    }
+}
+
+enum Command {
+    FORWARD,
+    UP,
+    DOWN
+}
+
+pub struct Instruction {
+    command: Command,
+    value: i32
+}
+
+fn parse_instruction(input: &str) -> Result<Instruction, Error> {
+    let parts: Vec<&str> = input.split(" ").collect();
+    if parts.len() != 2 {
+        return Err(Error::parse(format!("Invalid instruction: {}", input)));
+    }
+
+    let value = match number::parse_i32(parts[1]) {
+        Ok(v) => { v },
+        Err(_) => { return Err(Error::parse(format!("Invalid value in instruction {}: {}", input, parts[1]))) }
+    };
+
+    match parts[0] {
+        "forward" => {
+            Ok(Instruction { command: Command::FORWARD, value })
+        }
+        "up" => {
+            Ok(Instruction { command: Command::UP, value })
+        }
+        "down" => {
+            Ok(Instruction { command: Command::DOWN, value })
+        }
+        comm => Err(Error::parse(format!("Invalid command: {}", comm)))
+    }
+}
+
+fn to_instructions(input: &str) -> Result<Vec<Instruction>, Error> {
+    input.lines().enumerate().map(|(i, l)| parse_instruction(l).map_err(|e| e.at_line(i + 1))).collect()
+}
+
@@ -0,0 +1,208 @@
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+
+pub struct Day03;
+
+// A binary number from the diagnostic report, most significant bit first.
+type Bits = Vec<bool>;
+
+impl Solver for Day03 {
+    type Input = Vec<Bits>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let numbers = input.lines().enumerate()
+            .map(|(y, line)| line.chars().enumerate().map(|(x, c)| match c {
+                '1' => Ok(true),
+                '0' => Ok(false),
+                _ => Err(Error::parse(format!("Invalid binary character '{}'", c)).at(y + 1, x + 1))
+            }).collect::<Result<Bits, Error>>())
+            .collect::<Result<Vec<Bits>, Error>>()?;
+
+        // All numbers need the same number of bits, as the puzzles compare them bit by bit.
+        if let Some(first) = numbers.first() {
+            if let Some((y, number)) = numbers.iter().enumerate().find(|(_, n)| n.len() != first.len()) {
+                return Err(Error::parse(format!("Expected {} bits, but got {}", first.len(), number.len())).at_line(y + 1));
+            }
+        }
+
+        Ok(numbers)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        // Input are binary numbers
+        // Calculate gamma and epsilon
+        // gamma = reduce bits by taking the most frequent, epsilon takes less frequent (both are inverse of each other)
+        // answer is multiplying the resulting numbers in decimal form
+        let consumption = calculate_power_consumption(input)?;
+        let result = consumption.gamma * consumption.epsilon;
+        Ok(result.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let oxygen_rating = calculate_oxygen_rating(input)?;
+        let co2_rating = calculate_co2_rating(input)?;
+
+        let result = oxygen_rating * co2_rating;
+        Ok(result.into())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct PowerConsumption {
+    gamma: u128,
+    epsilon: u128,
+}
+
+fn calculate_power_consumption(inputs: &[Bits]) -> Result<PowerConsumption, Error> {
+    let word_size = match inputs.first() {
+        Some(w) => w.len(),
+        _ => return Err(Error::MissingInput("No input given".to_string()))
+    };
+    let mut counters: Vec<u128> = vec![0; word_size];
+
+    for input in inputs {
+        for i in 0..word_size {
+            counters[i] += input[i] as u128;
+        }
+    }
+
+    let mut gamma = 0;
+    let mut epsilon = 0;
+    for i in 0..word_size {
+        gamma <<= 1;
+        epsilon <<= 1;
+        if counters[i] * 2 > inputs.len() as u128 {
+            // More 1's than 0's
+            gamma += 1;
+        } else {
+            epsilon += 1;
+        }
+    }
+
+    Ok(PowerConsumption { gamma, epsilon })
+}
+
+// Whether 1 is the most common value of the given bit; if both are equally common, this counts as 1 too.
+fn get_most_common_bit(data: &[&Bits], bit: usize) -> bool {
+    let counter = data.iter().filter(|i| i[bit]).count();
+
+    counter * 2 >= data.len()
+}
+
+fn binary_to_number(binary: &[bool]) -> u128 {
+    binary.iter().fold(0, |result, bit| (result << 1) + *bit as u128)
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum RatingType {
+    OXYGEN,
+    CO2
+}
+
+fn get_rating(data: &[Bits], rating: RatingType) -> Result<u128, Error> {
+    let mut inputs: Vec<&Bits> = data.iter().collect();
+
+    let word_length = match inputs.first() {
+        Some(w) => w.len(),
+        None => return Err(Error::MissingInput("Expected inputs...".to_string()))
+    };
+
+    for i in 0..word_length {
+        let target_bit = get_most_common_bit(&inputs, i);
+        inputs.retain(|input| (input[i] == target_bit) == (rating == RatingType::OXYGEN));
+        if inputs.len() == 1 { break; }
+    }
+
+    match inputs.len() {
+        1 => Ok(binary_to_number(inputs[0])),
+        len => Err(Error::no_solution(format!("Did not reduce inputs to a single value, kept {}", len)))
+    }
+}
+
+fn calculate_oxygen_rating(data: &[Bits]) -> Result<u128, Error> {
+    // Check per bit, keep only those with the most frequent occurrence (equal numbers = use 1)
+    // Once one is left, that's the value.
+    get_rating(data, RatingType::OXYGEN)
+}
+
+fn calculate_co2_rating(data: &[Bits]) -> Result<u128, Error> {
+    // Check per bit, keep only those with the least frequent occurrence (equal numbers = use 1)
+    // Once one is left, that's the value.
+    get_rating(data, RatingType::CO2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::Solver;
+    use crate::days::y2021::day03::{binary_to_number, calculate_co2_rating, calculate_oxygen_rating, calculate_power_consumption, get_most_common_bit, Bits, Day03};
+    use crate::error::Error;
+
+    const EXAMPLE_INPUT: &str = "00100\n\
+                                 11110\n\
+                                 10110\n\
+                                 10111\n\
+                                 10101\n\
+                                 01111\n\
+                                 00111\n\
+                                 11100\n\
+                                 10000\n\
+                                 11001\n\
+                                 00010\n\
+                                 01010";
+
+    fn example_numbers() -> Vec<Bits> {
+        Day03::parse(EXAMPLE_INPUT).unwrap()
+    }
+
+    #[test]
+    fn power_consumption_example() {
+        let result = calculate_power_consumption(&example_numbers());
+        println!("{:?}", result);
+        assert!(result.is_ok(), "expected result to be ok");
+        let consumption = result.unwrap();
+        assert_eq!(consumption.gamma, 22);
+        assert_eq!(consumption.epsilon, 9);
+    }
+
+    #[test]
+    fn get_common_bit() {
+        let numbers = example_numbers();
+        let example_data: Vec<&Bits> = numbers.iter().collect();
+        assert!(get_most_common_bit(&example_data, 0));
+        assert!(!get_most_common_bit(&example_data, 1));
+        assert!(get_most_common_bit(&example_data, 2));
+        assert!(get_most_common_bit(&example_data, 3));
+    }
+
+    #[test]
+    fn oxygen_rating_example() {
+        let result = calculate_oxygen_rating(&example_numbers());
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        let rating = result.unwrap();
+        assert_eq!(rating, 23);
+    }
+
+    #[test]
+    fn co2_rating_example() {
+        let result = calculate_co2_rating(&example_numbers());
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        let rating = result.unwrap();
+        assert_eq!(rating, 10);
+    }
+
+    #[test]
+    fn binary_to_number_tests() {
+        let number = |s: &str| binary_to_number(&Day03::parse(s).unwrap()[0]);
+        assert_eq!(number("10110"), 22);
+        assert_eq!(number("00001"), 1);
+        assert_eq!(number("00000"), 0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Day03::parse("101\n121"), Err(Error::parse("Invalid binary character '2'").at(2, 2)));
+        assert_eq!(Day03::parse("101\n10"), Err(Error::parse("Expected 3 bits, but got 2").at_line(2)));
+    }
+}
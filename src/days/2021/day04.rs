@@ -1,39 +1,31 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number;
 
-pub const DAY4: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day04;
 
-fn puzzle1(input: &String) {
-    let puzzle = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+impl Solver for Day04 {
+    type Input = PuzzleInput;
 
-    let (winning_card, called_numbers) = match find_first_bingo(&puzzle) {
-        Some(v) => v,
-        None => panic!("Could not find a first bingo with the input?!")
-    };
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
 
-    let result = calculate_bingo_score(winning_card, called_numbers);
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let (winning_card, called_numbers) = find_first_bingo(puzzle)
+            .ok_or_else(|| Error::no_solution("Could not find a first bingo with the input?!"))?;
 
-fn puzzle2(input: &String) {
-    let puzzle = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+        let result = calculate_bingo_score(winning_card, called_numbers);
+        Ok(result.into())
+    }
 
-    let (winning_card, called_numbers) = match find_last_bingo(&puzzle) {
-        Some(v) => v,
-        None => panic!("Could not find a first bingo with the input?!")
-    };
+    fn part2(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let (winning_card, called_numbers) = find_last_bingo(puzzle)
+            .ok_or_else(|| Error::no_solution("Could not find a last bingo with the input?!"))?;
 
-    let result = calculate_bingo_score(winning_card, called_numbers);
-    println!("Puzzle 2 answer: {}", result);
+        let result = calculate_bingo_score(winning_card, called_numbers);
+        Ok(result.into())
+    }
 }
 
 const BINGO_SIZE: usize = 5;
@@ -89,25 +81,25 @@ impl IsMarked for Option<Cell> {
     }
 }
 
-fn parse_bingo_card(input: &str) -> Result<BingoCard, String> {
-    let lines: Vec<&str> = input.lines().filter(|l| !l.is_empty()).collect();
+fn parse_bingo_card(input: &str) -> Result<BingoCard, Error> {
+    let lines: Vec<(usize, &str)> = input.lines().enumerate().filter(|(_, l)| !l.is_empty()).collect();
     if lines.len() != BINGO_SIZE {
-        return Err(format!("Expected {} rows, but got {} instead.", BINGO_SIZE, lines.len()));
+        return Err(Error::parse(format!("Expected {} rows, but got {} instead.", BINGO_SIZE, lines.len())).at_line(1));
     }
 
     let mut cells: [[Option<Cell>; BINGO_SIZE]; BINGO_SIZE] = [[None; BINGO_SIZE]; BINGO_SIZE];
     for i in 0..BINGO_SIZE {
-        let line = lines[i];
-        let entries: Vec<&str> = line.split(' ').filter(|e| !e.is_empty()).collect();
+        let (line_index, line) = lines[i];
+        let entries = split_with_columns(line, ' ');
         if entries.len() != BINGO_SIZE {
-            return Err(format!("Expected {} entries, but got {} instead in '{}' at index {}", BINGO_SIZE, entries.len(), line, i));
+            return Err(Error::parse(format!("Expected {} entries, but got {} instead in '{}'", BINGO_SIZE, entries.len(), line)).at_line(line_index + 1));
         }
 
         for j in 0..BINGO_SIZE {
-            let entry = entries[j];
+            let (column, entry) = entries[j];
             match number::parse_u128(entry) {
                 Ok(value) => { cells[i][j] = Some(Cell { value }) }
-                Err(e) => return Err(e)
+                Err(e) => return Err(e.at(line_index + 1, column))
             }
         }
     }
@@ -115,12 +107,32 @@ fn parse_bingo_card(input: &str) -> Result<BingoCard, String> {
     return Ok(BingoCard { cells });
 }
 
-fn parse_bingo_cards(input: &str) -> Result<Vec<BingoCard>, String> {
-    input.split("\n\n").into_iter().map(|chunk| parse_bingo_card(chunk)).collect()
+// Splits the line on the given separator, skipping empty entries, and keeps the 1-based column of each entry.
+fn split_with_columns(line: &str, separator: char) -> Vec<(usize, &str)> {
+    let mut column = 1;
+    line.split(separator)
+        .map(|entry| {
+            let start = column;
+            column += entry.chars().count() + 1;
+            (start, entry)
+        })
+        .filter(|(_, entry)| !entry.is_empty())
+        .collect()
+}
+
+fn parse_bingo_cards(input: &str) -> Result<Vec<BingoCard>, Error> {
+    let mut cards = vec![];
+    let mut line_offset = 0;
+    for chunk in input.split("\n\n") {
+        cards.push(parse_bingo_card(chunk).map_err(|e| e.offset(line_offset, 0))?);
+        // Skip the lines of this card, and the empty line separating it from the next.
+        line_offset += chunk.lines().count() + 1;
+    }
+    Ok(cards)
 }
 
-fn parse_called_numbers(input: &str) -> Result<Vec<u128>, String> {
-    input.split(',').into_iter().map(|n| number::parse_u128(n)).collect()
+fn parse_called_numbers(input: &str) -> Result<Vec<u128>, Error> {
+    split_with_columns(input, ',').into_iter().map(|(column, n)| number::parse_u128(n).map_err(|e| e.at(1, column))).collect()
 }
 
 fn find_first_bingo(input: &PuzzleInput) -> Option<(&BingoCard, &[u128])> {
@@ -162,28 +174,28 @@ fn calculate_bingo_score(bingo_card: &BingoCard, called_numbers: &[u128]) -> u12
     return value * multiplier;
 }
 
-struct PuzzleInput {
+pub struct PuzzleInput {
     called_numbers: Vec<u128>,
     bingo_cards: Vec<BingoCard>,
 }
 
-fn parse_input(input: &str) -> Result<PuzzleInput, String> {
+fn parse_input(input: &str) -> Result<PuzzleInput, Error> {
     let lines: Vec<&str> = input.lines().collect();
-    let called_numbers = match parse_called_numbers(lines[0]) {
-        Ok(v) => v,
-        Err(e) => return Err(e)
-    };
-    let bingo_cards = match parse_bingo_cards(lines[2..].join("\n").as_str()) {
-        Ok(v) => v,
-        Err(e) => return Err(e)
-    };
+    // Missing parts of the input are reported at the line where they were expected.
+    let first = lines.first().ok_or_else(|| Error::parse("Expected the called numbers").at(1, 1))?;
+    let called_numbers = parse_called_numbers(first)?;
+    // The bingo cards start after the called numbers and an empty line.
+    if lines.len() < 3 {
+        return Err(Error::parse("Expected an empty line and bingo cards after the called numbers").at(lines.len() + 1, 1));
+    }
+    let bingo_cards = parse_bingo_cards(lines[2..].join("\n").as_str()).map_err(|e| e.offset(2, 0))?;
 
     return Ok(PuzzleInput { called_numbers, bingo_cards });
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day04::{Bingo, BingoCard, calculate_bingo_score, Cell, find_first_bingo, find_last_bingo, IsMarked, parse_bingo_card, parse_called_numbers, parse_input};
+    use crate::days::y2021::day04::{Bingo, BingoCard, calculate_bingo_score, Cell, find_first_bingo, find_last_bingo, IsMarked, parse_bingo_card, parse_called_numbers, parse_input};
 
     const EXAMPLE_INPUT: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\
         \n\
@@ -234,6 +246,7 @@ mod tests {
     fn test_parse_called_numbers() {
         assert_eq!(parse_called_numbers("1,34,21,76,42,98"), Ok(vec![1, 34, 21, 76, 42, 98]));
         assert_eq!(parse_called_numbers("1,34,21,a,42,98").is_err(), true);
+        assert_eq!(parse_called_numbers("1,34,21,a,42,98").map_err(|e| e.to_string()), Err("Parse error at line 1, column 9: Invalid u128 'a': invalid digit found in string".to_string()));
     }
 
     #[test]
@@ -256,6 +269,18 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_input_error_location() {
+        let result = parse_input(&EXAMPLE_INPUT.replace("19  8  7 25 23", "19  8  x 25 23"));
+        assert_eq!(result.err().map(|e| e.to_string()), Some("Parse error at line 11, column 8: Invalid u128 'x': invalid digit found in string".to_string()));
+
+        let result = parse_input(&EXAMPLE_INPUT.replace("20 11 10 24  4", "20 11 10 24"));
+        assert_eq!(result.err().map(|e| e.to_string()), Some("Parse error at line 12: Expected 5 entries, but got 4 instead in '20 11 10 24'".to_string()));
+
+        assert_eq!(parse_input("").err().map(|e| e.to_string()), Some("Parse error at line 1, column 1: Expected the called numbers".to_string()));
+        assert_eq!(parse_input("1,2").err().map(|e| e.to_string()), Some("Parse error at line 2, column 1: Expected an empty line and bingo cards after the called numbers".to_string()));
+    }
+
     #[test]
     fn test_find_first_bingo() {
         let input = parse_input(EXAMPLE_INPUT).unwrap();
@@ -283,4 +308,4 @@ mod tests {
         let input = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(calculate_bingo_score(&input.bingo_cards[2], &[7,4,9,5,11,17,23,2,0,14,21,24][..]), 4512)
     }
-}
\ No newline at end of file
+}
@@ -1,42 +1,42 @@
 use std::cmp::max;
 use std::collections::HashMap;
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::geometry::{Point, Line, Grid};
 
-pub const DAY5: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day05;
 
-fn puzzle1(input: &String) {
-    let map = match parse_lines(input) {
-        Err(e) => panic!("{}", e),
-        Ok(v) => build_vent_map(&v, true)
-    };
+impl Solver for Day05 {
+    type Input = Vec<Line>;
 
-    let result = map.values().iter().filter(|c| c.value >= 2).count();
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
 
-fn puzzle2(input: &String) {
-    let map = match parse_lines(input) {
-        Err(e) => panic!("{}", e),
-        Ok(v) => build_vent_map(&v, false)
-    };
+    fn part1(lines: &Self::Input) -> Result<Answer, Error> {
+        let map = build_vent_map(lines, true);
+
+        let result = map.values().iter().filter(|c| c.value >= 2).count();
+        Ok(result.into())
+    }
 
-    let result = map.values().iter().filter(|c| c.value >= 2).count();
-    println!("Puzzle 2 answer: {}", result);
+    fn part2(lines: &Self::Input) -> Result<Answer, Error> {
+        let map = build_vent_map(lines, false);
+
+        let result = map.values().iter().filter(|c| c.value >= 2).count();
+        Ok(result.into())
+    }
 }
 
-fn parse_point(input: &str) -> Result<Point, String> {
+fn parse_point(input: &str) -> Result<Point, Error> {
     input.parse()
 }
 
-fn parse_line(input: &str) -> Result<Line, String> {
+fn parse_line(input: &str) -> Result<Line, Error> {
     let parts: Vec<&str> = input.split(" -> ").collect();
-    if parts.len() != 2 { return Err(format!("Expected format x1,y1 -> x2,y2, but got: {}", input)); }
+    if parts.len() != 2 { return Err(Error::parse(format!("Expected format x1,y1 -> x2,y2, but got: {}", input))); }
 
-    let parse_result: Result<Vec<Point>, String> = parts.iter().map(|i| parse_point(i)).collect();
+    let parse_result: Result<Vec<Point>, Error> = parts.iter().map(|i| parse_point(i)).collect();
 
     match parse_result {
         Ok(v) => Ok(Line { start: v[0], end: v[1] }),
@@ -44,7 +44,7 @@ fn parse_line(input: &str) -> Result<Line, String> {
     }
 }
 
-fn parse_lines(input: &str) -> Result<Vec<Line>, String> {
+fn parse_lines(input: &str) -> Result<Vec<Line>, Error> {
     input.lines().filter(|l| !l.trim().is_empty()).map(|l| parse_line(l)).collect()
 }
 
@@ -88,7 +88,7 @@ fn is_horizontal_or_vertical_line(line: &Line) -> bool {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use crate::days::day05::{build_vent_map, Cell, Line, parse_line, parse_lines, parse_point, Point};
+    use crate::days::y2021::day05::{build_vent_map, Cell, Line, parse_line, parse_lines, parse_point, Point};
     use crate::util::geometry::Grid;
 
     #[test]
@@ -225,4 +225,4 @@ mod tests {
             (point(5, 9), Cell { value: 1 }),
         ])));
     }
-}
\ No newline at end of file
+}
@@ -1,34 +1,30 @@
 use std::cmp::{min};
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number;
 
-pub const DAY6: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day06;
 
-fn puzzle1(input: &String) {
-    let fish = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+impl Solver for Day06 {
+    type Input = Vec<u128>;
 
-    let result = get_fish_after_days(&fish, 80);
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
 
-fn puzzle2(input: &String) {
-    let fish = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+    fn part1(fish: &Self::Input) -> Result<Answer, Error> {
+        let result = get_fish_after_days(fish, 80);
+        Ok(result.into())
+    }
 
-    let result = get_fish_after_days(&fish, 256);
-    println!("Puzzle 2 answer: {}", result);
+    fn part2(fish: &Self::Input) -> Result<Answer, Error> {
+        let result = get_fish_after_days(fish, 256);
+        Ok(result.into())
+    }
 }
 
-fn parse_input(input: &str) -> Result<Vec<u128>, String> {
-    input.split(",").map(|i| number::parse_u128(i)).collect()
+fn parse_input(input: &str) -> Result<Vec<u128>, Error> {
+    input.trim().split(",").map(|i| number::parse_u128(i)).collect()
 }
 
 fn get_fish_after_days(initial_state: &Vec<u128>, num_days: u128) -> u128 {
@@ -86,7 +82,7 @@ fn simulate_bucket(buckets: [u128; 9], num_days: u128) -> [u128; 9] {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day06::{get_fish_after_days, parse_input};
+    use crate::days::y2021::day06::{get_fish_after_days, parse_input};
 
     const EXAMPLE_INPUT: &str = "3,4,3,1,2";
 
@@ -110,4 +106,4 @@ mod tests {
             println!("{}", day)
         }
     }
-}
\ No newline at end of file
+}
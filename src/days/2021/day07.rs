@@ -1,35 +1,31 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number;
 
-pub const DAY7: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day07;
 
-fn puzzle1(input: &String) {
-    let state = match parse_input(input) {
-        Err(e) => panic!("{}", e),
-        Ok(v) => v
-    };
+impl Solver for Day07 {
+    type Input = Vec<i128>;
 
-    let result = get_cheapest_position(&state, &DistanceMode::Puzzle1);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
 
-    println!("Puzzle 1 answer: {}", result.fuel);
-}
+    fn part1(state: &Self::Input) -> Result<Answer, Error> {
+        let result = get_cheapest_position(state, &DistanceMode::Puzzle1);
 
-fn puzzle2(input: &String) {
-    let state = match parse_input(input) {
-        Err(e) => panic!("{}", e),
-        Ok(v) => v
-    };
+        Ok(result.fuel.into())
+    }
 
-    let result = get_cheapest_position(&state, &DistanceMode::Puzzle2);
+    fn part2(state: &Self::Input) -> Result<Answer, Error> {
+        let result = get_cheapest_position(state, &DistanceMode::Puzzle2);
 
-    println!("Puzzle 2 answer: {} ({})", result.fuel, result.value);
+        Ok(result.fuel.into())
+    }
 }
 
-fn parse_input(input: &str) -> Result<Vec<i128>, String> {
-    input.split(",").map(|p| number::parse_i128(p)).collect()
+fn parse_input(input: &str) -> Result<Vec<i128>, Error> {
+    input.trim().split(",").map(|p| number::parse_i128(p)).collect()
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
@@ -54,7 +50,6 @@ fn get_cheapest_position(initial_state: &Vec<i128>, mode: &DistanceMode) -> Posi
             // For puzzle 2 it makes more sense to start at the average position
             let total = initial_state.iter().map(|v| v.clone() as f64).sum::<f64>();
             let average = total / (initial_state.len() as f64);
-            println!("Using average: {}, initial_guess: {}", average, average.round() as i128);
             average.round() as i128
         }
     };
@@ -100,8 +95,8 @@ fn get_total_fuel(position: i128, initial_states: &Vec<i128>, mode: &DistanceMod
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day07::{get_cheapest_position, get_total_fuel, Position};
-    use crate::days::day07::DistanceMode::{Puzzle1, Puzzle2};
+    use crate::days::y2021::day07::{get_cheapest_position, get_total_fuel, Position};
+    use crate::days::y2021::day07::DistanceMode::{Puzzle1, Puzzle2};
 
     const EXAMPLE_INPUT: [i128; 10] = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
 
@@ -121,4 +116,4 @@ mod tests {
         assert_eq!(get_cheapest_position(&EXAMPLE_INPUT.to_vec(), &Puzzle1), Position { value: 2, fuel: 37 });
         assert_eq!(get_cheapest_position(&EXAMPLE_INPUT.to_vec(), &Puzzle2), Position { value: 5, fuel: 168 });
     }
-}
\ No newline at end of file
+}
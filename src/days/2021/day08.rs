@@ -1,36 +1,29 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 
-pub const DAY8: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day08;
 
-fn puzzle1(input: &String) {
-    let screens = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+impl Solver for Day08 {
+    type Input = Vec<Screen>;
 
-    let result = count_simple_output_digits(screens);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(screens: &Self::Input) -> Result<Answer, Error> {
+        let result = count_simple_output_digits(screens);
 
-fn puzzle2(input: &String) {
-    let screens = match parse_input(input) {
-        Ok(v) => v,
-        Err(e) => panic!("{}", e)
-    };
+        Ok(result.into())
+    }
 
-    let result: Result<Vec<usize>, String> = screens.iter().map(|s| determine_mapping(s).and_then(|m| Ok(compute_screen_output(s, &m)))).collect();
-    let total_value: usize = match result {
-        Ok(v) => v.iter().sum(),
-        Err(e) => panic!("{}", e)
-    };
-    println!("Puzzle 2 answer: {}", total_value);
+    fn part2(screens: &Self::Input) -> Result<Answer, Error> {
+        let outputs: Vec<usize> = screens.iter().map(|s| determine_mapping(s).map(|m| compute_screen_output(s, &m))).collect::<Result<_, Error>>()?;
+        let total_value: usize = outputs.iter().sum();
+        Ok(total_value.into())
+    }
 }
 
-fn count_simple_output_digits(screens: Vec<Screen>) -> u128 {
+fn count_simple_output_digits(screens: &[Screen]) -> u128 {
     // Puzzle 1 is simple, count all occurrences of '1', '4', '7', or '8' in the outputs.
     // Those numbers have a unique amount of segments (2, 4, 3, and 7 respectively).
     screens.iter().map(|s| s.output.iter().map(|d| match d.len() {
@@ -54,20 +47,20 @@ fn compute_screen_output(screen: &Screen, mapping: &[String; 10]) -> usize {
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct Screen {
+pub struct Screen {
     all_digits: [String; 10],
     output: [String; 4],
 }
 
-fn parse_input(input: &str) -> Result<Vec<Screen>, String> {
+fn parse_input(input: &str) -> Result<Vec<Screen>, Error> {
     input.lines().map(|l| parse_screen(l)).collect()
 }
 
-fn parse_screen(input: &str) -> Result<Screen, String> {
+fn parse_screen(input: &str) -> Result<Screen, Error> {
     let split_result: Result<[&str; 2], Vec<_>> = input.split(" | ").collect::<Vec<_>>().try_into();
     let [input_digits, input_output] = match split_result {
         Ok([digits, output]) => [digits, output],
-        Err(parts) => return Err(format!("Expected exactly two parts, but got {}", parts.len()))
+        Err(parts) => return Err(Error::parse(format!("Expected exactly two parts, but got {}", parts.len())))
     };
 
     let parsed_digits = parse_digits(input_digits);
@@ -75,19 +68,19 @@ fn parse_screen(input: &str) -> Result<Screen, String> {
 
     match (parsed_digits, parsed_output) {
         (Ok(all_digits), Ok(output)) => to_screen(all_digits, output),
-        (Err(e1), Err(e2)) => Err(format!("{} | {}", e1, e2)),
+        (Err(e1), Err(e2)) => Err(Error::parse(format!("{} | {}", e1, e2))),
         (Err(e), _) | (_, Err(e)) => Err(e)
     }
 }
 
-fn parse_digits(input: &str) -> Result<Vec<String>, String> {
+fn parse_digits(input: &str) -> Result<Vec<String>, Error> {
     input.split(" ").map(|d| parse_digit(d)).collect()
 }
 
-fn parse_digit(input: &str) -> Result<String, String> {
-    let valid_chars: Result<Vec<char>, String> = input.chars().map(|c| match c {
+fn parse_digit(input: &str) -> Result<String, Error> {
+    let valid_chars: Result<Vec<char>, Error> = input.chars().map(|c| match c {
         'a' | 'b' | 'c' | 'd' | 'e' | 'f' | 'g' => Ok(c),
-        _ => Err(format!("Invalid character {}", c))
+        _ => Err(Error::parse(format!("Invalid character {}", c)))
     }).collect();
 
     match valid_chars {
@@ -96,18 +89,18 @@ fn parse_digit(input: &str) -> Result<String, String> {
     }
 }
 
-fn to_screen(all_digits: Vec<String>, output: Vec<String>) -> Result<Screen, String> {
-    let all_digits_arr: Result<[String; 10], String> = all_digits.try_into().map_err(|e: Vec<String>| format!("all_digits has wrong size {}", e.len()));
-    let output_arr: Result<[String; 4], String> = output.try_into().map_err(|e: Vec<String>| format!("output has wrong size {}", e.len()));
+fn to_screen(all_digits: Vec<String>, output: Vec<String>) -> Result<Screen, Error> {
+    let all_digits_arr: Result<[String; 10], Error> = all_digits.try_into().map_err(|e: Vec<String>| Error::parse(format!("all_digits has wrong size {}", e.len())));
+    let output_arr: Result<[String; 4], Error> = output.try_into().map_err(|e: Vec<String>| Error::parse(format!("output has wrong size {}", e.len())));
 
     match (all_digits_arr, output_arr) {
         (Ok(ada), Ok(oa)) => Ok(Screen { all_digits: ada, output: oa }),
-        (Err(e1), Err(e2)) => Err(format!("{} | {}", e1, e2)),
+        (Err(e1), Err(e2)) => Err(Error::parse(format!("{} | {}", e1, e2))),
         (Err(e), _) | (_, Err(e)) => Err(e)
     }
 }
 
-fn determine_mapping(screen: &Screen) -> Result<[String; 10], String> {
+fn determine_mapping(screen: &Screen) -> Result<[String; 10], Error> {
     Ok([
         find_digit_zero(&screen.all_digits).unwrap(),
         find_digit_one(&screen.all_digits).unwrap(),
@@ -128,7 +121,7 @@ fn sort_digit_chars(input: &String) -> String {
     chars.iter().collect()
 }
 
-fn find_digit<P>(inputs: &[String; 10], digit_name: &str, predicate: P) -> Result<String, String>
+fn find_digit<P>(inputs: &[String; 10], digit_name: &str, predicate: P) -> Result<String, Error>
     where P: FnMut(&String) -> bool
     {
     let matches: Vec<String> = inputs.into_iter()
@@ -137,7 +130,7 @@ fn find_digit<P>(inputs: &[String; 10], digit_name: &str, predicate: P) -> Resul
         .collect();
     match matches.len() {
         1 => Ok(matches[0].clone()),
-        len => Err(format!("Could not find a mapping for {}, found {} candidates", digit_name, len))
+        len => Err(Error::invalid_state(format!("Could not find a mapping for {}, found {} candidates", digit_name, len)))
     }
 }
 
@@ -150,35 +143,35 @@ fn is_same_digit(left: &String, right: &String) -> bool {
     left.len() == right.len() && get_overlap_chars(left, right).len() == left.len()
 }
 
-fn find_digit_zero(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_zero(inputs: &[String; 10]) -> Result<String, Error> {
     // All non-middle segments
     let middle_segment = get_middle_segment(inputs).unwrap();
     find_digit(inputs, "0", |d| d.len() == 6 && !d.contains(middle_segment))
 }
 
-fn find_digit_one(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_one(inputs: &[String; 10]) -> Result<String, Error> {
     // Only digit with two segments
     find_digit(inputs, "1", |d| d.len() == 2)
 }
 
-fn find_digit_two(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_two(inputs: &[String; 10]) -> Result<String, Error> {
     // The 5-length option that has only two matching characters with 4
     let four = find_digit_four(inputs).unwrap();
     find_digit(inputs, "5", |d| d.len() == 5 && get_overlap_chars(d, &four).len() == 2)
 }
 
-fn find_digit_three(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_three(inputs: &[String; 10]) -> Result<String, Error> {
     // We can find three by finding the only 5-length option that includes _both_ of digit one's characters.
     let one = find_digit_one(inputs).unwrap();
     find_digit(inputs, "1", |d| d.len() == 5 && one.chars().all(|c| d.contains(c)))
 }
 
-fn find_digit_four(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_four(inputs: &[String; 10]) -> Result<String, Error> {
     // The only one with 4 segments.
     find_digit(inputs, "4", |d| d.len() == 4)
 }
 
-fn find_digit_five(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_five(inputs: &[String; 10]) -> Result<String, Error> {
     // Since we can find three and two more easily, this is the 5-length string that is neither of those.
     let two = find_digit_two(inputs).unwrap();
     let three = find_digit_three(inputs).unwrap();
@@ -186,23 +179,23 @@ fn find_digit_five(inputs: &[String; 10]) -> Result<String, String> {
     find_digit(inputs, "5", |d| d.len() == 5 && d.ne(&two) && d.ne(&three))
 }
 
-fn find_digit_six(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_six(inputs: &[String; 10]) -> Result<String, Error> {
     // All except the top-right segment.
     let top_right_segment = get_top_right_segment(inputs).unwrap();
     find_digit(inputs, "6", |d| d.len() == 6 && !d.contains(top_right_segment))
 }
 
-fn find_digit_seven(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_seven(inputs: &[String; 10]) -> Result<String, Error> {
     // The only one with 3 segments.
     find_digit(inputs, "7", |d| d.len() == 3)
 }
 
-fn find_digit_eight(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_eight(inputs: &[String; 10]) -> Result<String, Error> {
     // The only one with all segments.
     find_digit(inputs, "8", |d| d.len() == 7)
 }
 
-fn find_digit_nine(inputs: &[String; 10]) -> Result<String, String> {
+fn find_digit_nine(inputs: &[String; 10]) -> Result<String, Error> {
     // The one 6-length that isn't 0 or 6 (too lazy to add a get-bottom-left)
     let zero = find_digit_zero(inputs).unwrap();
     let six = find_digit_six(inputs).unwrap();
@@ -210,18 +203,18 @@ fn find_digit_nine(inputs: &[String; 10]) -> Result<String, String> {
     find_digit(inputs, "9", |d| d.len() == 6 && d.ne(&zero) && d.ne(&six))
 }
 
-fn get_top_segment(inputs: &[String; 10]) -> Result<char, String> {
+fn get_top_segment(inputs: &[String; 10]) -> Result<char, Error> {
     // Top segment is the one extra char that seven has over one.
     let seven = find_digit_seven(inputs).unwrap();
     let one = find_digit_one(inputs).unwrap();
 
     match seven.chars().find(|c| !one.contains(c.clone())) {
         Some(c) => Ok(c),
-        None => Err("Could not determine the top-most bit".to_string())
+        None => Err(Error::invalid_state("Could not determine the top-most bit"))
     }
 }
 
-fn get_middle_segment(inputs: &[String; 10]) -> Result<char, String> {
+fn get_middle_segment(inputs: &[String; 10]) -> Result<char, Error> {
     let top_segment = get_top_segment(inputs).unwrap();
     let bottom_segment = get_bottom_segment(inputs).unwrap();
     let one = find_digit_one(inputs).unwrap();
@@ -232,11 +225,11 @@ fn get_middle_segment(inputs: &[String; 10]) -> Result<char, String> {
 
     match chars.len() {
         1 => Ok(chars[0]),
-        len => Err(format!("Could not get middle segment, {} chars matched: {}", len, chars.into_iter().map(String::from).collect::<String>()))
+        len => Err(Error::invalid_state(format!("Could not get middle segment, {} chars matched: {}", len, chars.into_iter().map(String::from).collect::<String>())))
     }
 }
 
-fn get_bottom_segment(inputs: &[String; 10]) -> Result<char, String> {
+fn get_bottom_segment(inputs: &[String; 10]) -> Result<char, Error> {
     // Bottom segment is the only char common in all 5-length inputs that is not in 4 (and isn't top)
     // 2 -> top, two of 4, bottom-left, bottom
     // 3 -> top, three of 4, bottom
@@ -248,10 +241,10 @@ fn get_bottom_segment(inputs: &[String; 10]) -> Result<char, String> {
         .map(|i| i.chars().filter(|c| c != &top_segment && !four_chars.contains(c)).collect::<Vec<char>>())
         .filter_map(|c| if c.len() == 1 { Some(c[0]) } else { None })
         .next()
-        .ok_or(format!("Could not determine bottom segment"))
+        .ok_or_else(|| Error::invalid_state("Could not determine bottom segment"))
 }
 
-fn get_top_right_segment(inputs: &[String; 10]) -> Result<char, String> {
+fn get_top_right_segment(inputs: &[String; 10]) -> Result<char, Error> {
     // The only character from 1 that is also in 2.
     let one_chars: Vec<char> = find_digit_one(inputs).unwrap().chars().collect();
     let two_chars: Vec<char> = find_digit_two(inputs).unwrap().chars().collect();
@@ -259,13 +252,13 @@ fn get_top_right_segment(inputs: &[String; 10]) -> Result<char, String> {
     let matches: Vec<char> = one_chars.into_iter().filter(|c| two_chars.contains(c)).collect();
     match matches.len() {
         1 => Ok(matches[0]),
-        len => Err(format!("Could not determine top-left segment, {} matches: {}", len, matches.into_iter().map(String::from).collect::<String>()))
+        len => Err(Error::invalid_state(format!("Could not determine top-left segment, {} matches: {}", len, matches.into_iter().map(String::from).collect::<String>())))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day08::{compute_screen_output, count_simple_output_digits, determine_mapping, find_digit_eight, find_digit_five, find_digit_four, find_digit_nine, find_digit_one, find_digit_seven, find_digit_six, find_digit_three, find_digit_two, find_digit_zero, get_bottom_segment, get_top_right_segment, get_top_segment, parse_input, parse_screen, Screen};
+    use crate::days::y2021::day08::{compute_screen_output, count_simple_output_digits, determine_mapping, find_digit_eight, find_digit_five, find_digit_four, find_digit_nine, find_digit_one, find_digit_seven, find_digit_six, find_digit_three, find_digit_two, find_digit_zero, get_bottom_segment, get_top_right_segment, get_top_segment, parse_input, parse_screen, Screen};
 
     const EXAMPLE_INPUT: &str = "\
         be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe\n\
@@ -291,7 +284,7 @@ mod tests {
     #[test]
     fn test_puzzle_1() {
         let input = parse_input(EXAMPLE_INPUT).unwrap();
-        assert_eq!(count_simple_output_digits(input), 26);
+        assert_eq!(count_simple_output_digits(&input), 26);
     }
 
     #[test]
@@ -341,4 +334,4 @@ mod tests {
         let mapping = determine_mapping(&screens[0]).unwrap();
         assert_eq!(compute_screen_output(&screens[0], &mapping), 8394);
     }
-}
\ No newline at end of file
+}
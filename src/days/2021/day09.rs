@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::geometry::{Point, Grid, Directions};
+use crate::util::search;
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer, Error> {
+        let values: Option<Vec<usize>> = map.find_low_spots().iter().map(|p| map.get(p).map(|v| v + 1)).collect();
+        let result = values.map(|v| v.iter().sum::<usize>())
+            .ok_or_else(|| Error::no_solution("Low spot outside of the map"))?;
+
+        Ok(result.into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer, Error> {
+        // Get the product of the three largest basins, size = number of cells
+        let mut basins: Vec<usize> = map.find_low_spots().into_iter().map(|p| map.get_basin(p).len()).collect();
+        // Sort inverted
+        basins.sort_by(|a, b| match a.cmp(b) {
+            Ordering::Less => Ordering::Greater,
+            Ordering::Greater => Ordering::Less,
+            Ordering::Equal => Ordering::Equal
+        });
+
+        if basins.len() < 3 {
+            return Err(Error::no_solution(format!("Expected at least three basins, but found {}", basins.len())));
+        }
+
+        let result = basins[0..3].iter().fold(1, |a, b| a * b);
+        Ok(result.into())
+    }
+}
+
+impl Grid<usize> {
+    fn find_low_spots(&self) -> Vec<Point> {
+        let mut result = vec![];
+
+        for point in self.points() {
+            match self.get(&point) {
+                Some(v) => {
+                    if self.get_adjacent(&point, Directions::NonDiagonal).into_iter().all(|a| v < a) {
+                        result.push(point)
+                    }
+                }
+                None => {}
+            }
+        }
+
+        result
+    }
+
+    fn get_basin(&self, p: Point) -> Vec<usize> {
+        // A basin is all connected points to 'p' that are less than 9
+        if self.get(&p).is_none_or(|v| v >= 9) {
+            return vec![];
+        }
+
+        search::reachable(p, |current| self.get_adjacent_points(current, Directions::NonDiagonal)
+            .into_iter()
+            .filter(|v| self.get(v).is_some_and(|v| v < 9)))
+            .iter()
+            .filter_map(|p| self.get(p))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::y2021::day09::{Grid};
+
+    fn get_example_map() -> Grid<usize> {
+        vec![
+            vec![2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
+            vec![3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
+            vec![9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
+            vec![8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
+            vec![9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
+        ].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_find_low_spots() {
+        let map = get_example_map();
+        let low_spots = map.find_low_spots();
+        assert_eq!(low_spots, vec![
+            (1, 0).into(),
+            (9, 0).into(),
+            (2, 2).into(),
+            (6, 4).into(),
+        ]);
+    }
+
+    #[test]
+    fn test_get_basin() {
+        let map = get_example_map();
+
+        assert_eq!(map.get_basin((1, 0).into()), vec![1, 2, 3]);
+        assert_eq!(map.get_basin((9, 0).into()), vec![0, 1, 2, 2, 1, 2, 3, 4, 4]);
+        assert_eq!(map.get_basin((2, 2).into()), vec![5, 8, 7, 8, 7, 8, 8, 7, 6, 6, 7, 8, 8, 8]);
+        assert_eq!(map.get_basin((6, 4).into()), vec![5, 6, 7, 8, 8, 7, 8, 6, 6]);
+    }
+}
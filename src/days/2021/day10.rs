@@ -1,24 +1,38 @@
 use std::collections::HashMap;
-use crate::days::Day;
-use crate::days::day10::ErrorType::Incomplete;
-
-pub const DAY10: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::days::y2021::day10::ErrorType::Incomplete;
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.lines().enumerate()
+            .map(|(y, line)| line.chars().enumerate().map(|(x, c)| match c {
+                '[' | '{' | '<' | '(' | ']' | '}' | '>' | ')' => Ok(c),
+                _ => Err(Error::parse(format!("Invalid character '{}'", c)).at(y + 1, x + 1))
+            }).collect())
+            .collect()
+    }
 
-fn puzzle1(input: &String) {
-    let error_score: u64 = input.lines().filter_map(|l| check_line(l)).map(|e| get_error_score(e)).sum();
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let error_score: u64 = input.iter().filter_map(|l| check_line(l)).map(|e| get_error_score(e)).sum();
 
-    println!("Puzzle 1 answer: {}", error_score);
-}
+        Ok(error_score.into())
+    }
 
-fn puzzle2(input: &String) {
-    let mut completion_scores: Vec<u64> = input.lines().filter_map(|l| check_line(l)).filter_map(|e| get_completion_score(e)).collect();
-    completion_scores.sort();
-    let result = completion_scores[completion_scores.len()/2];
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let mut completion_scores: Vec<u64> = input.iter().filter_map(|l| check_line(l)).filter_map(|e| get_completion_score(e)).collect();
+        if completion_scores.is_empty() {
+            return Err(Error::no_solution("No incomplete lines in input"));
+        }
+        completion_scores.sort();
+        let result = completion_scores[completion_scores.len()/2];
 
-    println!("Puzzle 2 answer: {}", result);
+        Ok(result.into())
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -38,7 +52,7 @@ struct SyntaxError {
     expected: Option<String>,
 }
 
-fn check_line(line: &str) -> Option<SyntaxError> {
+fn check_line(chars: &[char]) -> Option<SyntaxError> {
     // The allowed syntax here is to have pairs of characters nested pretty much like HTML or code in general
     // [], (), <>, and {} are the valid pairs
     // [], [()], and {{([(())])}} are examples of valid inputs.
@@ -52,7 +66,6 @@ fn check_line(line: &str) -> Option<SyntaxError> {
     ]);
 
     let mut stack: Vec<char> = vec![];
-    let chars: Vec<char> = line.chars().collect();
 
     for i in 0..chars.len() {
         let current = chars[i];
@@ -98,7 +111,7 @@ fn check_line(line: &str) -> Option<SyntaxError> {
         None
     } else {
         let expected: String = stack.iter().rev().map(|c| format!("{}", match_table.get(c).unwrap())).collect::<String>();
-        Some(SyntaxError { index: line.len(), invalid: None, error_type: ErrorType::Incomplete, expected: Some(expected) })
+        Some(SyntaxError { index: chars.len(), invalid: None, error_type: ErrorType::Incomplete, expected: Some(expected) })
     }
 }
 
@@ -133,8 +146,10 @@ fn get_completion_score(error: SyntaxError) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day10::{check_line, get_completion_score, get_error_score, SyntaxError};
-    use crate::days::day10::ErrorType::{Corrupt, Incomplete};
+    use crate::days::Solver;
+    use crate::days::y2021::day10::{get_completion_score, get_error_score, Day10, SyntaxError};
+    use crate::error::Error;
+    use crate::days::y2021::day10::ErrorType::{Corrupt, Incomplete};
 
     const EXAMPLE_INPUT: &str = "\
         [({(<(())[]>[[{[]{<()<>>\n\
@@ -149,6 +164,16 @@ mod tests {
         <{([{{}}[<[[[<>{}]]]>[]]\
     ";
 
+    fn check_line(line: &str) -> Option<SyntaxError> {
+        super::check_line(&line.chars().collect::<Vec<char>>())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day10::parse("[]\n(<>)"), Ok(vec![vec!['[', ']'], vec!['(', '<', '>', ')']]));
+        assert_eq!(Day10::parse("[]\n(<x>)"), Err(Error::parse("Invalid character 'x'").at(2, 3)));
+    }
+
     #[test]
     fn test_check_line() {
         assert_eq!(check_line("[]"), None);
@@ -173,17 +198,18 @@ mod tests {
 
     #[test]
     fn test_example_input_corrupt_score() {
-        let lines: Vec<&str> = EXAMPLE_INPUT.lines().collect();
+        let lines = Day10::parse(EXAMPLE_INPUT).unwrap();
 
-        let score: u64 = lines.into_iter().filter_map(|l| check_line(l)).map(|e| get_error_score(e)).sum();
+        let score: u64 = lines.iter().filter_map(|l| super::check_line(l)).map(|e| get_error_score(e)).sum();
         assert_eq!(score, 26397);
     }
 
     #[test]
     fn test_example_input_completion_score() {
-        let mut scores: Vec<u64> = EXAMPLE_INPUT.lines().filter_map(|l| check_line(l)).filter_map(|e| get_completion_score(e)).collect();
+        let lines = Day10::parse(EXAMPLE_INPUT).unwrap();
+        let mut scores: Vec<u64> = lines.iter().filter_map(|l| super::check_line(l)).filter_map(|e| get_completion_score(e)).collect();
         scores.sort();
         let result = scores[scores.len() / 2];
         assert_eq!(result, 288957)
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,31 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::geometry::{Directions, Grid, Point};
 
-pub const DAY11: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day11;
 
-fn puzzle1(input: &String) {
-    let mut grid: Grid<usize> = input.parse().unwrap();
+impl Solver for Day11 {
+    type Input = Grid<usize>;
 
-    let result = get_flashes_after(&mut grid, 100);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(grid: &Self::Input) -> Result<Answer, Error> {
+        let mut grid = grid.clone();
 
-fn puzzle2(input: &String) {
-    let mut grid: Grid<usize> = input.parse().unwrap();
+        let result = get_flashes_after(&mut grid, 100);
+
+        Ok(result.into())
+    }
 
-    let result = find_step_all_flash(&mut grid);
+    fn part2(grid: &Self::Input) -> Result<Answer, Error> {
+        let mut grid = grid.clone();
 
-    println!("Puzzle 2 answer: {}", result);
+        let result = find_step_all_flash(&mut grid);
+
+        Ok(result.into())
+    }
 }
 
 impl Grid<usize> {
@@ -108,7 +114,7 @@ fn find_step_all_flash(grid: &mut Grid<usize>) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day11::{find_step_all_flash, get_flashes_after, run_flash_cycle};
+    use crate::days::y2021::day11::{find_step_all_flash, get_flashes_after, run_flash_cycle};
     use crate::util::geometry::Grid;
 
     fn get_example_grid() -> Grid<usize> {
@@ -208,4 +214,4 @@ mod tests {
         assert_eq!(find_step_all_flash(&mut grid), 195);
         assert_eq!(grid, vec![vec![0; 10]; 10].try_into().unwrap())
     }
-}
\ No newline at end of file
+}
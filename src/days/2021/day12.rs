@@ -1,26 +1,28 @@
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::RandomState;
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 
-pub const DAY12: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day12;
 
-fn puzzle1(input: &String) {
-    let system: CaveSystem = input.as_str().try_into().unwrap();
+impl Solver for Day12 {
+    type Input = CaveSystem;
 
-    let paths = system.paths(system.start(), &vec![], 1);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.try_into()
+    }
 
-    println!("Puzzle 1 answer: {}", paths.len());
-}
+    fn part1(system: &Self::Input) -> Result<Answer, Error> {
+        let paths = system.paths(system.start()?, &vec![], 1);
 
-fn puzzle2(input: &String) {
-    let system: CaveSystem = input.as_str().try_into().unwrap();
+        Ok(paths.len().into())
+    }
 
-    let paths = system.paths(system.start(), &vec![], 2);
+    fn part2(system: &Self::Input) -> Result<Answer, Error> {
+        let paths = system.paths(system.start()?, &vec![], 2);
 
-    println!("Puzzle 2 answer: {}", paths.len());
+        Ok(paths.len().into())
+    }
 }
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
@@ -29,39 +31,39 @@ enum CaveType {
     Small,
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
-struct Cave<'t> {
-    name: &'t str,
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+struct Cave {
+    name: String,
     ty: CaveType,
 }
 
 #[derive(Eq, PartialEq, Debug)]
-struct CaveSystem<'t> {
-    caves: Vec<Cave<'t>>,
-    connections: HashMap<Cave<'t>, Vec<Cave<'t>>>,
+pub struct CaveSystem {
+    caves: Vec<Cave>,
+    connections: HashMap<Cave, Vec<Cave>>,
 }
 
-fn add_connection<'t>(map: &mut HashMap<Cave<'t>, Vec<Cave<'t>>>, from: Cave<'t>, to: Cave<'t>) {
-    let mut existing = match map.get(&from) {
-        Some(v) if v.contains(&to) => return,
+fn add_connection(map: &mut HashMap<Cave, Vec<Cave>>, from: &Cave, to: &Cave) {
+    let mut existing = match map.get(from) {
+        Some(v) if v.contains(to) => return,
         Some(v) => v.clone(),
         None => vec![]
     };
-    existing.push(to);
-    map.insert(from, existing);
+    existing.push(to.clone());
+    map.insert(from.clone(), existing);
 }
 
-impl<'t> CaveSystem<'t> {
-    fn add(&mut self, from: Cave<'t>, to: Cave<'t>) {
+impl CaveSystem {
+    fn add(&mut self, from: Cave, to: Cave) {
+        add_connection(&mut self.connections, &from, &to);
+        add_connection(&mut self.connections, &to, &from);
+
         if !self.caves.contains(&from) { self.caves.push(from); }
         if !self.caves.contains(&to) { self.caves.push(to); }
-
-        add_connection(&mut self.connections, from, to);
-        add_connection(&mut self.connections, to, from);
     }
 
-    fn start(&self) -> &Cave {
-        self.caves.iter().find(|c| c.name == "start").unwrap()
+    fn start(&self) -> Result<&Cave, Error> {
+        self.caves.iter().find(|c| c.name == "start").ok_or_else(|| Error::invalid_state("There is no 'start' cave"))
     }
 
     fn paths(&self, from: &Cave, visited: &Vec<&Cave>, max_visits_small: usize) -> Vec<String> {
@@ -87,7 +89,7 @@ impl<'t> CaveSystem<'t> {
         new_visited.push(from);
 
         if from.name == "end" {
-            let parts: Vec<String> = new_visited.iter().map(|c| c.name.to_owned()).collect();
+            let parts: Vec<&str> = new_visited.iter().map(|c| c.name.as_str()).collect();
             return vec![parts.join(",")];
         }
 
@@ -102,47 +104,49 @@ impl<'t> CaveSystem<'t> {
     }
 }
 
-struct Connection<'t> {
-    from: Cave<'t>,
-    to: Cave<'t>,
+struct Connection {
+    from: Cave,
+    to: Cave,
 }
 
-impl<'t> TryFrom<&'t str> for Cave<'t> {
-    type Error = String;
+impl TryFrom<&str> for Cave {
+    type Error = Error;
 
-    fn try_from(input: &'t str) -> Result<Self, Self::Error> {
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let is_big = input.chars().all(|c| c.is_ascii_alphabetic() && c.is_uppercase());
         let is_small = input.chars().all(|c| c.is_ascii_alphabetic() && c.is_lowercase());
 
         if is_big == is_small {
-            Err(format!("Mixed case is not allowed for cave: {}", input))
+            Err(Error::parse(format!("Mixed case is not allowed for cave: {}", input)))
         } else if is_big {
-            Ok(Cave { name: input, ty: CaveType::Big })
+            Ok(Cave { name: input.to_string(), ty: CaveType::Big })
         } else {
-            Ok(Cave { name: input, ty: CaveType::Small })
+            Ok(Cave { name: input.to_string(), ty: CaveType::Small })
         }
     }
 }
 
-impl<'t> TryFrom<&'t str> for Connection<'t> {
-    type Error = String;
+impl TryFrom<&str> for Connection {
+    type Error = Error;
 
-    fn try_from(input: &'t str) -> Result<Self, Self::Error> {
-        let caves: Result<Vec<Cave>, String> = input.split("-").map(|p| p.try_into()).collect();
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let caves: Result<Vec<Cave>, Error> = input.split("-").map(|p| p.try_into()).collect();
 
         match caves {
             Err(e) => Err(e),
             Ok(v) if v.len() == 2 => Ok(Connection { from: v[0].clone(), to: v[1].clone() }),
-            Ok(v) => Err(format!("Expected <cave>-<cave>, but matched {} caves in {}", v.len(), input)),
+            Ok(v) => Err(Error::parse(format!("Expected <cave>-<cave>, but matched {} caves in {}", v.len(), input))),
         }
     }
 }
 
-impl<'t> TryFrom<&'t str> for CaveSystem<'t> {
-    type Error = String;
+impl TryFrom<&str> for CaveSystem {
+    type Error = Error;
 
-    fn try_from(input: &'t str) -> Result<Self, Self::Error> {
-        let result: Result<Vec<Connection>, String> = input.lines().map(|l| l.try_into()).collect();
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let result: Result<Vec<Connection>, Error> = input.lines().enumerate()
+            .map(|(i, l)| Connection::try_from(l).map_err(|e| e.at(i + 1, 1)))
+            .collect();
         let raw_connections = match result {
             Err(e) => return Err(e),
             Ok(v) => v
@@ -154,6 +158,11 @@ impl<'t> TryFrom<&'t str> for CaveSystem<'t> {
             system.add(connection.from, connection.to)
         }
 
+        // Reported at the line where a connection from or to it would have been expected.
+        if system.start().is_err() {
+            return Err(Error::parse("Expected a connection from or to the 'start' cave").at(input.lines().count() + 1, 1));
+        }
+
         Ok(system)
     }
 }
@@ -161,8 +170,9 @@ impl<'t> TryFrom<&'t str> for CaveSystem<'t> {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use crate::days::day12::{Cave, CaveSystem};
-    use crate::days::day12::CaveType::{Big, Small};
+    use crate::days::y2021::day12::{Cave, CaveSystem};
+    use crate::days::y2021::day12::CaveType::{Big, Small};
+    use crate::error::Error;
 
     const EXAMPLE_INPUT_SMALL: &str = "\
         start-A\n\
@@ -208,13 +218,19 @@ mod tests {
         start-RW\
     ";
 
-    fn big(name: &str) -> Cave { Cave { name, ty: Big } }
+    fn big(name: &str) -> Cave { Cave { name: name.to_string(), ty: Big } }
+
+    fn small(name: &str) -> Cave { Cave { name: name.to_string(), ty: Small } }
 
-    fn small(name: &str) -> Cave { Cave { name, ty: Small } }
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(CaveSystem::try_from("A-b\nb-end"), Err(Error::parse("Expected a connection from or to the 'start' cave").at(3, 1)));
+        assert_eq!(CaveSystem::try_from("start-A\nA-b-c"), Err(Error::parse("Expected <cave>-<cave>, but matched 3 caves in A-b-c").at(2, 1)));
+    }
 
     #[test]
     fn test_parse() {
-        let system: Result<CaveSystem, String> = EXAMPLE_INPUT_SMALL.try_into();
+        let system: Result<CaveSystem, Error> = EXAMPLE_INPUT_SMALL.try_into();
         assert_eq!(system, Ok(CaveSystem {
             caves: vec![small("start"), big("A"), small("b"), small("c"), small("d"), small("end")],
             connections: HashMap::from([
@@ -232,7 +248,7 @@ mod tests {
     fn test_paths_small() {
         let system: CaveSystem = EXAMPLE_INPUT_SMALL.try_into().unwrap();
 
-        let mut paths = system.paths(system.start(), &vec![], 1);
+        let mut paths = system.paths(system.start().unwrap(), &vec![], 1);
         paths.sort();
         assert_eq!(paths.len(), 10);
         assert_eq!(paths, vec![
@@ -248,7 +264,7 @@ mod tests {
             "start,b,end",
         ]);
 
-        let mut paths_2 = system.paths(system.start(), &vec![], 2);
+        let mut paths_2 = system.paths(system.start().unwrap(), &vec![], 2);
         paths_2.sort();
         assert_eq!(paths_2.len(), 36);
         assert_eq!(paths_2, vec![
@@ -295,7 +311,7 @@ mod tests {
     fn test_paths_large() {
         let system: CaveSystem = EXAMPLE_INPUT_LARGE.try_into().unwrap();
 
-        let mut paths = system.paths(system.start(), &vec![], 1);
+        let mut paths = system.paths(system.start().unwrap(), &vec![], 1);
         paths.sort();
 
         assert_eq!(paths.len(), 19);
@@ -321,15 +337,15 @@ mod tests {
             "start,kj,dc,end",
         ]);
 
-        assert_eq!(system.paths(system.start(), &vec![], 2).len(), 103);
+        assert_eq!(system.paths(system.start().unwrap(), &vec![], 2).len(), 103);
     }
 
     #[test]
     fn test_paths_huge() {
         let system: CaveSystem = EXAMPLE_INPUT_HUGE.try_into().unwrap();
 
-        let paths = system.paths(system.start(), &vec![], 1);
+        let paths = system.paths(system.start().unwrap(), &vec![], 1);
         assert_eq!(paths.len(), 226);
-        assert_eq!(system.paths(system.start(), &vec![], 2).len(), 3509);
+        assert_eq!(system.paths(system.start().unwrap(), &vec![], 2).len(), 3509);
     }
-}
\ No newline at end of file
+}
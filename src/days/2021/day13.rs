@@ -1,41 +1,52 @@
 use std::fmt;
 use std::str::FromStr;
 use regex::Regex;
-use crate::days::Day;
-use crate::util::geometry::Point;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::geometry::{Bounds, Point, SparseGrid};
 use crate::util::number;
-use crate::util::collection::{CollectionExtension};
 
-pub const DAY13: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day13;
 
-fn puzzle1(input: &String) {
-    let paper: Paper = input.parse().unwrap();
+impl Solver for Day13 {
+    type Input = Paper;
 
-    let folded_once = paper.fold();
-
-    println!("Puzzle 1 answer: {}", folded_once.dots.len());
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-fn puzzle2(input: &String) {
-    let mut paper: Paper = input.parse().unwrap();
+    fn part1(paper: &Self::Input) -> Result<Answer, Error> {
+        let folded_once = paper.fold();
 
-    while !paper.instructions.is_empty() {
-        paper = paper.fold();
+        Ok(folded_once.dots.len().into())
     }
 
-    println!("Puzzle 2 answer:\n{}", paper);
+    fn part2(paper: &Self::Input) -> Result<Answer, Error> {
+        let mut paper = paper.clone();
+
+        while !paper.instructions.is_empty() {
+            paper = paper.fold();
+        }
+
+        Ok(Answer::Text(paper.to_string().trim_end().to_string()))
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Paper {
-    dots: Vec<Point>,
+pub struct Paper {
+    dots: SparseGrid<bool>,
     instructions: Vec<FoldInstruction>,
 }
 
 impl Paper {
+    fn new(dots: Vec<Point>, instructions: Vec<FoldInstruction>) -> Self {
+        let mut grid = SparseGrid::new(false);
+        for dot in dots {
+            grid.set(dot, true);
+        }
+        Paper { dots: grid, instructions }
+    }
+
     /// Consume the top-most fold instruction, and return a new Paper representing the result.
     fn fold(&self) -> Paper {
         if self.instructions.is_empty() {
@@ -45,26 +56,18 @@ impl Paper {
         let instruction = self.instructions[0];
         let instructions = self.instructions[1..].to_vec();
 
-        let dots: Vec<Point> = self.dots.iter().map(|p| instruction.apply(p)).collect();
-        Paper { dots: dots.deduplicate(), instructions }
+        // Dots that end up on top of each other are merged by the grid.
+        let dots: Vec<Point> = self.dots.cells().map(|(p, _)| instruction.apply(p)).collect();
+        Paper::new(dots, instructions)
     }
 }
 
 impl fmt::Display for Paper {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let max_x = self.dots.iter().map(|p| p.x).max().unwrap_or(0);
-        let max_y = self.dots.iter().map(|p| p.y).max().unwrap_or(0);
-
-        for y in 0..=max_y {
-            for x in 0..=max_x {
-                if self.dots.contains(&(x, y).into()) {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            write!(f, "\n")?;
-        }
+        // The paper starts at (0, 0), even if there are no dots in the top rows or left columns.
+        let bounds = self.dots.bounds();
+        let window = Bounds::from_tlbr(0, 0, bounds.bottom() - 1, bounds.right() - 1);
+        writeln!(f, "{}", self.dots.window(window, |dot| if *dot { '#' } else { '.' }))?;
 
         if !self.instructions.is_empty() {
             write!(f, "\n")?;
@@ -78,16 +81,16 @@ impl fmt::Display for Paper {
 }
 
 impl FromStr for Paper {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split("\n\n").collect();
-        if parts.len() != 2 { return Err(format!("Invalid format: {}", s)); }
+        if parts.len() != 2 { return Err(Error::parse(format!("Invalid format: {}", s))); }
 
-        let dots: Vec<Point> = parts[0].lines().map(|l| l.parse()).collect::<Result<Vec<Point>, String>>()?;
-        let instructions: Vec<FoldInstruction> = parts[1].lines().map(|l| l.parse()).collect::<Result<Vec<FoldInstruction>, String>>()?;
+        let dots: Vec<Point> = parts[0].lines().map(|l| l.parse()).collect::<Result<Vec<Point>, Error>>()?;
+        let instructions: Vec<FoldInstruction> = parts[1].lines().map(|l| l.parse()).collect::<Result<Vec<FoldInstruction>, Error>>()?;
 
-        Ok(Paper { dots, instructions })
+        Ok(Paper::new(dots, instructions))
     }
 }
 
@@ -124,28 +127,28 @@ impl fmt::Display for FoldInstruction {
 }
 
 impl FromStr for FoldInstruction {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let regex = match Regex::new("^fold along ([xy])=(\\d+)$") {
             Ok(r) => r,
-            Err(e) => return Err(format!("Could build regex?! {}", e))
+            Err(e) => return Err(Error::invalid_state(format!("Could build regex?! {}", e)))
         };
 
         let captures = match regex.captures(s) {
             Some(c) => c,
-            None => return Err(format!("Invalid fold instruction: {}", s))
+            None => return Err(Error::parse(format!("Invalid fold instruction: {}", s)))
         };
         let axis = match captures.get(1) {
             Some(v) if v.as_str() == "x" => FoldAxis::X,
             Some(v) if v.as_str() == "y" => FoldAxis::Y,
-            Some(v) => return Err(format!("Invalid fold axis {} in {}", v.as_str(), s)),
-            None => return Err(format!("Invalid fold instruction: {}", s))
+            Some(v) => return Err(Error::parse(format!("Invalid fold axis {} in {}", v.as_str(), s))),
+            None => return Err(Error::parse(format!("Invalid fold instruction: {}", s)))
         };
         let value = match captures.get(2).map(|v| number::parse_usize(v.as_str())) {
             Some(Ok(v)) => v,
             Some(Err(e)) => return Err(e),
-            None => return Err(format!("Invalid fold instruction: {}", s))
+            None => return Err(Error::parse(format!("Invalid fold instruction: {}", s)))
         };
         Ok(FoldInstruction { axis, value })
     }
@@ -153,8 +156,9 @@ impl FromStr for FoldInstruction {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day13::{FoldInstruction, Paper};
-    use crate::days::day13::FoldAxis::{X, Y};
+    use crate::days::y2021::day13::{FoldInstruction, Paper};
+    use crate::days::y2021::day13::FoldAxis::{X, Y};
+    use crate::error::Error;
 
     const EXAMPLE_INPUT: &str = "\
         6,10\n\
@@ -182,10 +186,10 @@ mod tests {
 
     #[test]
     fn test_format() {
-        let paper: Paper = Paper {
-            dots: vec![(0, 1).into(), (2, 2).into(), (3, 0).into(), (1, 3).into()],
-            instructions: vec![FoldInstruction { axis: X, value: 2 }],
-        };
+        let paper: Paper = Paper::new(
+            vec![(0, 1).into(), (2, 2).into(), (3, 0).into(), (1, 3).into()],
+            vec![FoldInstruction { axis: X, value: 2 }],
+        );
 
         assert_eq!(format!("{}", paper), "\
             ...#\n\
@@ -199,9 +203,9 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let paper: Result<Paper, String> = EXAMPLE_INPUT.parse();
-        assert_eq!(paper, Ok(Paper {
-            dots: vec![
+        let paper: Result<Paper, Error> = EXAMPLE_INPUT.parse();
+        assert_eq!(paper, Ok(Paper::new(
+            vec![
                 (6, 10).into(),
                 (0, 14).into(),
                 (9, 10).into(),
@@ -221,11 +225,11 @@ mod tests {
                 (8, 10).into(),
                 (9, 0).into(),
             ],
-            instructions: vec![
+            vec![
                 FoldInstruction { axis: Y, value: 7 },
                 FoldInstruction { axis: X, value: 5 },
             ],
-        }))
+        )))
     }
 
     #[test]
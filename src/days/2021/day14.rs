@@ -1,28 +1,30 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::str::FromStr;
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::collection::CollectionExtension;
 
-pub const DAY14: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day14;
 
-fn puzzle1(input: &String) {
-    let puzzle: Puzzle = input.parse().unwrap();
+impl Solver for Day14 {
+    type Input = Puzzle;
 
-    let result = compute_score(&puzzle, 10);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let result = compute_score(puzzle, 10)?;
 
-fn puzzle2(input: &String) {
-    let puzzle: Puzzle = input.parse().unwrap();
+        Ok(result.into())
+    }
 
-    let result = compute_score(&puzzle, 40);
+    fn part2(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let result = compute_score(puzzle, 40)?;
 
-    println!("Puzzle 2 answer: {}", result);
+        Ok(result.into())
+    }
 }
 
 struct PairInsertion {
@@ -31,20 +33,22 @@ struct PairInsertion {
 }
 
 impl FromStr for PairInsertion {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(" -> ").collect();
-        if parts.len() != 2 || parts[0].len() != 2 || parts[1].len() != 1 {
-            return Err(format!("Invalid format: {}", s));
+        let parts: Vec<Vec<char>> = s.split(" -> ").map(|p| p.chars().collect()).collect();
+        match parts.as_slice() {
+            [pair, insertion] => match (pair.as_slice(), insertion.as_slice()) {
+                ([a, b], [c]) => Ok(PairInsertion { pair: [*a, *b], insertion: *c }),
+                _ => Err(Error::parse(format!("Invalid format: {}", s)))
+            },
+            _ => Err(Error::parse(format!("Invalid format: {}", s)))
         }
-
-        Ok(PairInsertion { pair: parts[0].chars().collect::<Vec<char>>().try_into().unwrap(), insertion: parts[1].chars().next().unwrap() })
     }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct Puzzle {
+pub struct Puzzle {
     template: String,
     pair_insertions: HashMap<[char; 2], char>,
 }
@@ -66,7 +70,7 @@ struct CacheEntry {
     pairs: Vec<CachePair>
 }
 
-fn compute_score(puzzle: &Puzzle, num_steps: usize) -> usize {
+fn compute_score(puzzle: &Puzzle, num_steps: usize) -> Result<usize, Error> {
     // The length of the template grows too fast to use the simple way used above... (Of course. Why would it be simple?)
     // Idea:
     // For each pair, build a tree by computing the left and right pairs, until `num_steps` depth, counting characters.
@@ -89,6 +93,12 @@ fn compute_score(puzzle: &Puzzle, num_steps: usize) -> usize {
         vec![[pair[0], insert_char], [insert_char, pair[1]]].into_iter().flat_map(|p| handle_pair(p, num_steps - 1, pairs)).collect()
     }
 
+    // Every pair that turns up needs a rule, as the cache below only has entries for the pairs that have one.
+    fn get_entry<'c>(cache: &'c HashMap<CacheKey, CacheEntry>, levels: usize, pair: &[char; 2]) -> Result<&'c CacheEntry, Error> {
+        cache.get(&CacheKey { levels, pair: *pair })
+            .ok_or_else(|| Error::invalid_state(format!("No insertion rule for pair {}{}", pair[0], pair[1])))
+    }
+
     let mut cache: HashMap<CacheKey, CacheEntry> = HashMap::new();
 
     // Level goes exponential:
@@ -107,13 +117,13 @@ fn compute_score(puzzle: &Puzzle, num_steps: usize) -> usize {
                 cache.insert(CacheKey { levels: cache_level, pair: pair.0.clone() }, CacheEntry { pairs: cache_pairs });
             } else {
                 // We should be able to get the last level from cache, and build a new entry based on it.
-                let entry = cache.get(&CacheKey { levels: previous_level, pair: pair.0.clone() }).unwrap();
+                let entry = get_entry(&cache, previous_level, pair.0)?;
 
                 // let mut values = entry.values.clone();
                 let mut pairs: HashMap<[char; 2], usize> = HashMap::new();
 
                 for pair in &entry.pairs {
-                    let entry = cache.get(&CacheKey { levels: previous_level, pair: pair.pair }).unwrap();
+                    let entry = get_entry(&cache, previous_level, &pair.pair)?;
                     for entry_pair in &entry.pairs {
                         pairs.insert(entry_pair.pair, pairs.get(&entry_pair.pair).unwrap_or(&0) + entry_pair.occurrences * pair.occurrences);
                     }
@@ -125,36 +135,37 @@ fn compute_score(puzzle: &Puzzle, num_steps: usize) -> usize {
         }
     }
 
-    fn compute(pair: &[char; 2], occurrences: usize, cache: &HashMap<CacheKey, CacheEntry>, num_steps: usize) -> Vec<CachePair> {
-        if num_steps == 0 { return vec![{ CachePair { pair: pair.clone(), occurrences }}]; }
+    fn compute(pair: &[char; 2], occurrences: usize, cache: &HashMap<CacheKey, CacheEntry>, num_steps: usize) -> Result<Vec<CachePair>, Error> {
+        if num_steps == 0 { return Ok(vec![{ CachePair { pair: pair.clone(), occurrences }}]); }
 
         let cache_steps = if num_steps >= 32 { 32 } else if num_steps >= 16 { 16 } else if num_steps >= 8 { 8 } else if num_steps >= 4 { 4 } else if num_steps >= 2 { 2 } else { 1 };
 
-        let entry = cache.get(&CacheKey { levels: cache_steps, pair: pair.clone() }).unwrap();
+        let entry = get_entry(cache, cache_steps, pair)?;
 
         let mut pairs: HashMap<[char; 2], usize> = HashMap::new();
 
         for entry_pair in &entry.pairs {
-            for cache_pair in compute(&entry_pair.pair, entry_pair.occurrences * occurrences, cache, num_steps - cache_steps) {
+            for cache_pair in compute(&entry_pair.pair, entry_pair.occurrences * occurrences, cache, num_steps - cache_steps)? {
                 pairs.insert(cache_pair.pair, pairs.get(&cache_pair.pair).unwrap_or(&0) + cache_pair.occurrences);
             }
         }
 
-        pairs.iter().map(|p| CachePair { pair: p.0.clone(), occurrences: p.1.clone() }).collect()
+        Ok(pairs.iter().map(|p| CachePair { pair: p.0.clone(), occurrences: p.1.clone() }).collect())
     }
 
     let mut buckets: HashMap<[char; 2], usize> = HashMap::new();
 
     let chars: Vec<char> = puzzle.template.chars().collect();
+    let first = *chars.first().ok_or_else(|| Error::invalid_state("The polymer template is empty"))?;
     for i in 0..chars.len() - 1 {
-        for pair in compute(&[chars[i], chars[i+1]], 1, &cache, num_steps) {
+        for pair in compute(&[chars[i], chars[i+1]], 1, &cache, num_steps)? {
             buckets.insert(pair.pair, buckets.get(&pair.pair).unwrap_or(&0) + pair.occurrences);
         }
     }
 
     // Character counts, we need to count the first character of the initial template ourselves, and then all second characters of the pairs.
     let mut char_counts: HashMap<char, usize> = HashMap::new();
-    char_counts.insert(chars[0], 1);
+    char_counts.insert(first, 1);
     for bucket_entry in buckets.iter() {
         let bucket_char = bucket_entry.0[1];
         char_counts.insert(bucket_char, char_counts.get(&bucket_char).unwrap_or(&0) + bucket_entry.1);
@@ -164,24 +175,30 @@ fn compute_score(puzzle: &Puzzle, num_steps: usize) -> usize {
     let max: usize = scores.iter().max_by_key(|p| p.1).map(|p| p.1).unwrap_or(0);
     let min: usize = scores.iter().min_by_key(|p| p.1).map(|p| p.1).unwrap_or(0);
 
-    max - min
+    Ok(max - min)
 }
 
 impl FromStr for Puzzle {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split("\n\n").collect();
         if parts.len() != 2 {
-            return Err(format!("Invalid format: {}", s));
+            return Err(Error::parse(format!("Invalid format: {}", s)));
         }
 
         let template = parts[0].trim().to_owned();
-        let mut pair_insertions = HashMap::new();
+        if template.is_empty() {
+            return Err(Error::parse("Expected a polymer template").at(1, 1));
+        }
 
-        parts[1].lines().filter(|l| !l.trim().is_empty())
-            .filter_map(|l| l.parse::<PairInsertion>().ok())
-            .for_each(|pi| { pair_insertions.insert(pi.pair, pi.insertion); });
+        // The rules start after the template and the empty line below it.
+        let first_rule_line = parts[0].lines().count() + 2;
+        let mut pair_insertions = HashMap::new();
+        for (i, line) in parts[1].lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let pi: PairInsertion = line.parse().map_err(|e: Error| e.at_line(first_rule_line + i))?;
+            pair_insertions.insert(pi.pair, pi.insertion);
+        }
 
         Ok(Puzzle { template, pair_insertions })
     }
@@ -190,7 +207,8 @@ impl FromStr for Puzzle {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use crate::days::day14::{Puzzle, compute_score};
+    use crate::days::y2021::day14::{Puzzle, compute_score};
+    use crate::error::Error;
 
     const EXAMPLE_INPUT: &str = "\
         NNCB\n\
@@ -215,7 +233,7 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let result: Result<Puzzle, String> = EXAMPLE_INPUT.parse();
+        let result: Result<Puzzle, Error> = EXAMPLE_INPUT.parse();
 
         assert_eq!(result, Ok(Puzzle {
             template: String::from("NNCB"),
@@ -244,7 +262,20 @@ mod tests {
     fn test_score() {
         let puzzle: Puzzle = EXAMPLE_INPUT.parse().unwrap();
 
-        assert_eq!(compute_score(&puzzle, 10), 1588);
-        assert_eq!(compute_score(&puzzle, 40), 2188189693529);
+        assert_eq!(compute_score(&puzzle, 10), Ok(1588));
+        assert_eq!(compute_score(&puzzle, 40), Ok(2188189693529));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("\n\nAB -> C".parse::<Puzzle>(), Err(Error::parse("Expected a polymer template").at(1, 1)));
+        assert_eq!("AB\n\nAB -> C\nABC -> D".parse::<Puzzle>(), Err(Error::parse("Invalid format: ABC -> D").at_line(4)));
+        assert_eq!("AB\n\nAB -> ".parse::<Puzzle>(), Err(Error::parse("Invalid format: AB -> ").at_line(3)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_missing_rule() {
+        let puzzle: Puzzle = "NN\n\nCC -> C".parse().unwrap();
+        assert_eq!(compute_score(&puzzle, 10), Err(Error::invalid_state("No insertion rule for pair NN")));
+    }
+}
@@ -1,93 +1,79 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use crate::days::Day;
-use crate::util::geometry::{Directions, Grid, Point};
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::geometry::{Bounds, Directions, Grid, Point};
+use crate::util::render::{Picture, Pixel};
+use crate::util::search::{self, SearchResult};
 
-pub const DAY15: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day15;
 
-fn puzzle1(input: &String) {
-    let grid: Grid<usize> = input.parse().unwrap();
+impl Solver for Day15 {
+    type Input = Grid<usize>;
 
-    let score = find_lowest_risk_path_cost(&grid).unwrap();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-    println!("Puzzle 1 answer: {}", score);
-}
+    fn part1(grid: &Self::Input) -> Result<Answer, Error> {
+        let path = find_lowest_risk_path(grid)?
+            .ok_or_else(|| Error::no_solution("Could not find a path through the cave"))?;
 
-fn puzzle2(input: &String) {
-    let grid: Grid<usize> = input.parse().unwrap();
-    let no_the_real_grid = build_real_map(&grid);
+        Ok(path.cost.into())
+    }
 
-    let score = find_lowest_risk_path_cost(&no_the_real_grid).unwrap();
+    fn part2(grid: &Self::Input) -> Result<Answer, Error> {
+        let no_the_real_grid = build_real_map(grid);
 
-    println!("Puzzle 2 answer: {}", score);
-}
+        let path = find_lowest_risk_path(&no_the_real_grid)?
+            .ok_or_else(|| Error::no_solution("Could not find a path through the cave"))?;
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-struct DijkstraEntry {
-    distance: usize,
-    node: Point,
-}
-
-impl Ord for DijkstraEntry {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
-            .then_with(|| self.node.x.cmp(&other.node.x))
-            .then_with(|| self.node.y.cmp(&other.node.y))
+        Ok(path.cost.into())
     }
-}
 
-impl PartialOrd for DijkstraEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    // The map with the path of the lowest risk on it; for part 2, that's the real (5 times larger) map.
+    fn render(grid: &Self::Input, part: i32) -> Result<Option<Picture>, Error> {
+        let grid = if part == 2 { build_real_map(grid) } else { grid.clone() };
+        let path = find_lowest_risk_path(&grid)?
+            .ok_or_else(|| Error::no_solution("Could not find a path through the cave"))?;
+
+        Ok(Some(draw_path(&grid, &path.path)))
     }
 }
 
-fn find_lowest_risk_path_cost(grid: &Grid<usize>) -> Option<usize> {
+// The path from the top-left to the bottom-right with the lowest total risk. Its cost is that risk; the risk of the start
+// itself is not counted, as it is never entered.
+fn find_lowest_risk_path(grid: &Grid<usize>) -> Result<Option<SearchResult<Point>>, Error> {
     // Start at top-left, end at bottom-right
     // How bad will brute force perform? -> Very, very bad. 😂
-    // Since this is pretty much shortest path, let's see if I can build Dijkstra.
-
-    fn dijkstra(grid: &Grid<usize>, target: &Point) -> Option<usize> {
-        let mut dist = HashMap::new();
-        let mut queue = BinaryHeap::new();
-
-        let start: Point = (0, 0).into();
-        dist.insert(start, 0 as usize);
-        queue.push(DijkstraEntry { node: start, distance: 0 });
-
-        while let Some(DijkstraEntry { node, distance }) = queue.pop() {
-            if node.eq(target) {
-                // We found the part to target, we're done.
-                return Some(distance);
-            }
-
-            // If there already is a distance for this node that is less, we can skip this
-            if let Some(current_distance) = dist.get(&node) {
-                if distance.gt(current_distance) {
-                    continue;
-                }
-            }
+    // Since this is pretty much shortest path, Dijkstra it is: the cost of a step is the risk of the cell entered.
+    let start: Point = (0, 0).into();
+    let target = Point { x: grid.bounds.right() - 1, y: grid.bounds.bottom() - 1 };
 
-            for neighbor in grid.get_adjacent_points(&node, Directions::NonDiagonal) {
-                let distance_to_neighbor = grid.get(&neighbor).unwrap();
-                let total_distance_through_current = distance + distance_to_neighbor;
-                let current_distance = dist.get(&neighbor).unwrap_or(&usize::MAX);
+    // Every point of the grid is a state, so they are numbered by their position in it.
+    let bounds = grid.bounds;
+    search::dijkstra_indexed(
+        start,
+        |node| grid.get_adjacent_points(node, Directions::NonDiagonal).into_iter()
+            .map(|neighbor| (neighbor, grid.get(&neighbor).unwrap_or(0))),
+        |node| node.eq(&target),
+        |node| (node.y - bounds.top) as usize * bounds.width + (node.x - bounds.left) as usize,
+        bounds.width * bounds.height,
+    )
+}
 
-                if total_distance_through_current.lt(current_distance) {
-                    queue.push(DijkstraEntry { node: neighbor.clone(), distance: total_distance_through_current });
-                    dist.insert(neighbor.clone(), total_distance_through_current);
-                }
-            }
+// The risk levels as digits, in shades of grey from dark (low risk) to light (high risk), with the path in red.
+fn draw_path(grid: &Grid<usize>, path: &[Point]) -> Picture {
+    let mut picture = Picture::with_bounds(grid.bounds);
+    for (point, risk) in grid.entries() {
+        let shade = (risk * 255 / 9) as u8;
+        picture.set(point, Pixel { symbol: char::from_digit(risk as u32, 10).unwrap_or('?'), color: [shade, shade, shade], highlight: false });
+    }
+    for point in path {
+        if let Some(pixel) = picture.get_mut(point) {
+            pixel.color = [255, 0, 0];
+            pixel.highlight = true;
         }
-
-        None
     }
-
-    let target = Point { x: grid.bounds.right() - 1, y: grid.bounds.bottom() - 1 };
-    dijkstra(grid, &target)
+    picture
 }
 
 fn build_real_map(segment: &Grid<usize>) -> Grid<usize> {
@@ -96,7 +82,8 @@ fn build_real_map(segment: &Grid<usize>) -> Grid<usize> {
     // To stitch the real map together, we copy the initial segment to the right/bottom, while increasing
     // all costs by 1. A 9 will go back to 1.
 
-    let mut cells = HashMap::new();
+    let bounds = Bounds { top: 0, left: 0, width: segment.bounds.width * 5, height: segment.bounds.height * 5 };
+    let mut grid = Grid::with_bounds(bounds);
 
     for x in 0..5 {
         for y in 0..5 {
@@ -104,18 +91,18 @@ fn build_real_map(segment: &Grid<usize>) -> Grid<usize> {
                 let new_x = point.x + (x * segment.bounds.width) as isize;
                 let new_y = point.y + (y * segment.bounds.height) as isize;
                 let value = segment.get(&point).unwrap_or(0) + x + y;
-                cells.insert((new_x,new_y).into(), if value > 9 { value % 9 } else { value });
+                grid.set((new_x, new_y).into(), if value > 9 { value % 9 } else { value });
             }
         }
     }
 
-    Grid::new(cells)
+    grid
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day15::{build_real_map, find_lowest_risk_path_cost};
-    use crate::util::geometry::{Grid};
+    use crate::days::y2021::day15::{build_real_map, draw_path, find_lowest_risk_path};
+    use crate::util::geometry::{Directions, Grid, Point};
 
     const EXAMPLE_INPUT: &str = "\
         1163751742\n\
@@ -132,7 +119,26 @@ mod tests {
     #[test]
     fn test_find_lowest_risk_path_cost() {
         let grid: Grid<usize> = EXAMPLE_INPUT.parse().unwrap();
-        assert_eq!(find_lowest_risk_path_cost(&grid), Some(40));
+        let path = find_lowest_risk_path(&grid).unwrap().unwrap();
+        assert_eq!(path.cost, 40);
+
+        // A path of adjacent points from corner to corner, with the risks of all but the start adding up to the cost.
+        assert_eq!(path.path.first(), Some(&Point { x: 0, y: 0 }));
+        assert_eq!(path.path.last(), Some(&Point { x: 9, y: 9 }));
+        assert!(path.path.windows(2).all(|w| grid.get_adjacent_points(&w[0], Directions::NonDiagonal).contains(&w[1])));
+        assert_eq!(path.path[1..].iter().map(|p| grid.get(p).unwrap()).sum::<usize>(), 40);
+    }
+
+    #[test]
+    fn test_draw_path() {
+        let grid: Grid<usize> = vec![vec![1, 9], vec![2, 3]].try_into().unwrap();
+        let picture = draw_path(&grid, &[(0, 0).into(), (0, 1).into(), (1, 1).into()]);
+
+        let symbols: String = picture.values().iter().map(|p| p.symbol).collect();
+        assert_eq!(symbols, "1923");
+        let highlighted: Vec<bool> = picture.values().iter().map(|p| p.highlight).collect();
+        assert_eq!(highlighted, vec![true, false, true, true]);
+        assert_eq!(picture.get(&(1, 0).into()).unwrap().color, [255, 255, 255]);
     }
 
     #[test]
@@ -208,6 +214,6 @@ mod tests {
         let grid: Grid<usize> = EXAMPLE_INPUT.parse().unwrap();
         let real_grid = build_real_map(&grid);
 
-        assert_eq!(find_lowest_risk_path_cost(&real_grid), Some(315));
+        assert_eq!(find_lowest_risk_path(&real_grid).unwrap().map(|p| p.cost), Some(315));
     }
-}
\ No newline at end of file
+}
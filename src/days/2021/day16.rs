@@ -1,25 +1,29 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number::parse_binary;
 
-pub const DAY16: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day16;
 
-fn puzzle1(input: &String) {
-    let packet = Packet::parse(input).unwrap();
+impl Solver for Day16 {
+    type Input = Packet;
 
-    let result = packet.sum_versions();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Packet::from_binary(&decode(input.trim_end())?)
+            .ok_or_else(|| Error::parse("Could not parse packet"))
+    }
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(packet: &Self::Input) -> Result<Answer, Error> {
+        let result = packet.sum_versions();
 
-fn puzzle2(input: &String) {
-    let packet = Packet::parse(input).unwrap();
+        Ok(result.into())
+    }
 
-    let result = packet.compute().unwrap();
+    fn part2(packet: &Self::Input) -> Result<Answer, Error> {
+        let result = packet.compute()
+            .ok_or_else(|| Error::no_solution("Could not compute packet value"))?;
 
-    println!("Puzzle 2 answer: {}", result);
+        Ok(result.into())
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -29,19 +33,23 @@ enum PacketData {
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Packet {
+pub struct Packet {
     version: usize,
     type_id: usize,
     data: PacketData,
 }
 
 impl Packet {
+    #[cfg(test)]
     fn parse(input: &str) -> Option<Packet> {
-        let binary = decode(input);
+        Packet::from_binary(&decode(input.trim_end()).ok()?)
+    }
+
+    fn from_binary(binary: &str) -> Option<Packet> {
         let mut pointer = 0;
 
         // The input is always one big packet, which contains multiple packets.
-        read_packet(&binary, &mut pointer)
+        read_packet(binary, &mut pointer)
     }
 
     fn sum_versions(&self) -> usize {
@@ -174,10 +182,10 @@ fn read_packet(input: &str, pointer: &mut usize) -> Option<Packet> {
     Some(Packet { version, type_id, data })
 }
 
-fn decode(input: &str) -> String {
+fn decode(input: &str) -> Result<String, Error> {
     // Initially, the input is HEX-encoded binary data.
     // For parsing the packets, it's easier to have the binary string, so we'll be converting the data here.
-    input.chars().map(|c| match c {
+    input.chars().enumerate().map(|(i, c)| Ok(match c {
         '0' => "0000",
         '1' => "0001",
         '2' => "0010",
@@ -194,17 +202,19 @@ fn decode(input: &str) -> String {
         'D' => "1101",
         'E' => "1110",
         'F' => "1111",
-        _ => panic!("Illegal hex char: {}", c)
-    }).collect::<String>()
+        _ => return Err(Error::parse(format!("Illegal hex char: {}", c)).at(1, i + 1))
+    })).collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day16::{decode, Packet, PacketData, read_bit, read_literal, read_packet};
+    use crate::days::y2021::day16::{decode, Packet, PacketData, read_bit, read_literal, read_packet};
+    use crate::error::Error;
 
     #[test]
     fn test_decode() {
-        assert_eq!(decode("38006F45291200"), "00111000000000000110111101000101001010010001001000000000");
+        assert_eq!(decode("38006F45291200"), Ok("00111000000000000110111101000101001010010001001000000000".to_string()));
+        assert_eq!(decode("38006G"), Err(Error::parse("Illegal hex char: G").at(1, 6)));
     }
 
     #[test]
@@ -283,4 +293,4 @@ mod tests {
         assert_eq!(Packet::parse("9C005AC2F8F0").and_then(|p| p.compute()), Some(0));
         assert_eq!(Packet::parse("9C0141080250320F1802104A08").and_then(|p| p.compute()), Some(1));
     }
-}
\ No newline at end of file
+}
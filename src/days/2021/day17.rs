@@ -1,31 +1,37 @@
 use std::ops::{RangeInclusive};
 use std::str::FromStr;
 use regex::Regex;
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number::parse_isize;
 
-pub const DAY17: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day17;
 
-fn puzzle1(input: &String) {
-    let area: TargetArea = input.parse().unwrap();
-    let trajectory = calculate_highest_trajectory(&area).unwrap();
-    let result = trajectory.get_top();
+impl Solver for Day17 {
+    type Input = TargetArea;
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-fn puzzle2(input: &String) {
-    let area: TargetArea = input.parse().unwrap();
-    let trajectories = get_all_possible_trajectories(&area).unwrap();
+    fn part1(area: &Self::Input) -> Result<Answer, Error> {
+        let trajectory = calculate_highest_trajectory(area)
+            .ok_or_else(|| Error::no_solution("No trajectory hits the target area"))?;
+        let result = trajectory.get_top();
+
+        Ok(result.into())
+    }
 
-    println!("Puzzle 2 answer: {}", trajectories.len());
+    fn part2(area: &Self::Input) -> Result<Answer, Error> {
+        let trajectories = get_all_possible_trajectories(area)
+            .ok_or_else(|| Error::no_solution("No trajectory hits the target area"))?;
+
+        Ok(trajectories.len().into())
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct TargetArea {
+pub struct TargetArea {
     x: RangeInclusive<isize>,
     y: RangeInclusive<isize>,
 }
@@ -45,16 +51,16 @@ impl TargetArea {
 }
 
 impl FromStr for TargetArea {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let regex = Regex::new("^target area: x=(?P<x1>-?\\d+)..(?P<x2>-?\\d+), y=(?P<y1>-?\\d+)..(?P<y2>-?\\d+)$").map_err(|e| format!("{}", e))?;
+        let regex = Regex::new("^target area: x=(?P<x1>-?\\d+)..(?P<x2>-?\\d+), y=(?P<y1>-?\\d+)..(?P<y2>-?\\d+)$").map_err(|e| Error::invalid_state(format!("{}", e)))?;
 
-        let captures = regex.captures(s).ok_or(format!("Couldn't parse input target: {}", s))?;
-        let x1 = captures.name("x1").ok_or(format!("Missing x1 in {}", s)).and_then(|v| parse_isize(v.as_str()))?;
-        let x2 = captures.name("x2").ok_or(format!("Missing x2 in {}", s)).and_then(|v| parse_isize(v.as_str()))?;
-        let y1 = captures.name("y1").ok_or(format!("Missing y1 in {}", s)).and_then(|v| parse_isize(v.as_str()))?;
-        let y2 = captures.name("y2").ok_or(format!("Missing y2 in {}", s)).and_then(|v| parse_isize(v.as_str()))?;
+        let captures = regex.captures(s.trim()).ok_or_else(|| Error::parse(format!("Couldn't parse input target: {}", s)))?;
+        let x1 = captures.name("x1").ok_or_else(|| Error::parse(format!("Missing x1 in {}", s))).and_then(|v| parse_isize(v.as_str()))?;
+        let x2 = captures.name("x2").ok_or_else(|| Error::parse(format!("Missing x2 in {}", s))).and_then(|v| parse_isize(v.as_str()))?;
+        let y1 = captures.name("y1").ok_or_else(|| Error::parse(format!("Missing y1 in {}", s))).and_then(|v| parse_isize(v.as_str()))?;
+        let y2 = captures.name("y2").ok_or_else(|| Error::parse(format!("Missing y2 in {}", s))).and_then(|v| parse_isize(v.as_str()))?;
 
         Ok(TargetArea { x: x1..=x2, y: y1..=y2 })
     }
@@ -192,7 +198,7 @@ fn get_all_possible_trajectories(area: &TargetArea) -> Option<Vec<Trajectory>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day17::{calculate_highest_trajectory, get_all_possible_trajectories, get_steps_for_y_hit, get_xs_for_y, TargetArea, Trajectory};
+    use crate::days::y2021::day17::{calculate_highest_trajectory, get_all_possible_trajectories, get_steps_for_y_hit, get_xs_for_y, TargetArea, Trajectory};
 
     #[test]
     fn test_target_area_from_str() {
@@ -254,4 +260,4 @@ mod tests {
         assert_eq!(missing, vec![]);
         assert_eq!(extra, vec![]);
     }
-}
\ No newline at end of file
+}
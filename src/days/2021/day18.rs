@@ -1,26 +1,30 @@
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 
-pub const DAY18: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day18;
 
-fn puzzle1(input: &String) {
-    let numbers = parse_puzzle_input(input).unwrap();
+impl Solver for Day18 {
+    type Input = Vec<SnailNumber>;
 
-    let result = sum_list(&numbers).unwrap().magnitude();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_puzzle_input(input)
+    }
 
-    println!("Puzzle 1 answer: {}", result);
-}
+    fn part1(numbers: &Self::Input) -> Result<Answer, Error> {
+        let result = sum_list(numbers)
+            .ok_or_else(|| Error::no_solution("Cannot sum an empty list"))?
+            .magnitude();
 
-fn puzzle2(input: &String) {
-    let numbers = parse_puzzle_input(input).unwrap();
+        Ok(result.into())
+    }
 
-    let max_magnitude = numbers.iter().flat_map(|x|
-        numbers.iter().filter(|y| x.ne(y)).map(|y| x.add(y).reduce().magnitude())
-    ).max().unwrap();
+    fn part2(numbers: &Self::Input) -> Result<Answer, Error> {
+        let max_magnitude = numbers.iter().flat_map(|x|
+            numbers.iter().filter(|y| x.ne(y)).map(|y| x.add(y).reduce().magnitude())
+        ).max().ok_or_else(|| Error::no_solution("Need at least two different numbers"))?;
 
-    println!("Puzzle 2 answer: {}", max_magnitude);
+        Ok(max_magnitude.into())
+    }
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
@@ -30,7 +34,7 @@ struct SnailEntry {
 }
 
 #[derive(Eq, PartialEq, Clone)]
-struct SnailNumber {
+pub struct SnailNumber {
     entries: Vec<SnailEntry>,
 }
 
@@ -132,7 +136,7 @@ impl From<Vec<(usize, usize)>> for SnailNumber {
 }
 
 impl std::str::FromStr for SnailNumber {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let chars: Vec<char> = s.chars().collect();
@@ -145,12 +149,12 @@ impl std::str::FromStr for SnailNumber {
                 ']' => current_level -= 1,
                 c if c.is_digit(10) => {
                     entries.push(SnailEntry {
-                        value: c.to_digit(10).ok_or(format!("Invalid token '{}' in SnailNumber", c)).and_then(|v| v.try_into().map_err(|e| format!("{}", e)))?,
+                        value: c.to_digit(10).ok_or_else(|| Error::parse(format!("Invalid token '{}' in SnailNumber", c))).and_then(|v| v.try_into().map_err(|e| Error::parse(format!("{}", e))))?,
                         level: current_level,
                     });
                 }
                 ',' => { /* ignore */ }
-                inv => return Err(format!("Invalid token '{}' in SnailNumber", inv))
+                inv => return Err(Error::parse(format!("Invalid token '{}' in SnailNumber", inv)))
             }
         }
 
@@ -196,7 +200,7 @@ impl std::fmt::Debug for SnailNumber {
     }
 }
 
-fn parse_puzzle_input(input: &str) -> Result<Vec<SnailNumber>, String> {
+fn parse_puzzle_input(input: &str) -> Result<Vec<SnailNumber>, Error> {
     input.lines().filter(|l| !l.is_empty()).map(|l| l.parse()).collect()
 }
 
@@ -206,7 +210,7 @@ fn sum_list(list: &Vec<SnailNumber>) -> Option<SnailNumber> {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day18::{parse_puzzle_input, SnailNumber, sum_list};
+    use crate::days::y2021::day18::{parse_puzzle_input, SnailNumber, sum_list};
 
     #[test]
     fn test_display() {
@@ -1,37 +1,41 @@
 use regex::Regex;
-use crate::days::Day;
-use crate::days::day19::FacingDirection::{XNeg, XPos, YNeg, YPos, ZNeg, ZPos};
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::days::y2021::day19::FacingDirection::{XNeg, XPos, YNeg, YPos, ZNeg, ZPos};
+use crate::util::cancel;
 use crate::util::collection::CollectionExtension;
 use crate::util::geometry::Point3D;
 
-pub const DAY19: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day19;
 
-fn puzzle1(input: &String) {
-    let scanners = parse_input(input).unwrap();
-    let beacons = map_all_beacons(&scanners);
+impl Solver for Day19 {
+    type Input = Vec<Scanner>;
 
-    println!("Puzzle 1 answer: {}", beacons.len());
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
+
+    fn part1(scanners: &Self::Input) -> Result<Answer, Error> {
+        let beacons = map_all_beacons(scanners)?;
 
-fn puzzle2(input: &String) {
-    let scanners = parse_input(input).unwrap();
+        Ok(beacons.len().into())
+    }
 
-    let mapped = map_scanners(&scanners);
+    fn part2(scanners: &Self::Input) -> Result<Answer, Error> {
+        let mapped = map_scanners(scanners)?;
 
-    let mut max_manhattan = 0;
-    for i in 0..mapped.len() {
-        let current = &mapped[i];
-        if let Some(max) = mapped.iter().skip(i)
-            .map(|other| current.location.manhattan(&other.location))
-            .max() {
-            max_manhattan = max_manhattan.max(max);
+        let mut max_manhattan = 0;
+        for i in 0..mapped.len() {
+            let current = &mapped[i];
+            if let Some(max) = mapped.iter().skip(i)
+                .map(|other| current.location.manhattan(&other.location))
+                .max() {
+                max_manhattan = max_manhattan.max(max);
+            }
         }
-    }
 
-    println!("Puzzle 2 answer: {}", max_manhattan);
+        Ok(max_manhattan.into())
+    }
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
@@ -72,24 +76,33 @@ fn get_overlapping_values(left: &Vec<Point3D>, right: &Vec<Point3D>) -> Vec<Poin
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct Scanner {
+pub struct Scanner {
     name: String,
     location: Point3D,
     points: Vec<Point3D>,
 }
 
-fn parse_scanner(input: &str) -> Result<Scanner, String> {
+fn parse_scanner(input: &str) -> Result<Scanner, Error> {
     // Skip the first line, with the `--- scanner # ---`
     let lines: Vec<&str> = input.lines().collect();
-    let regex = Regex::new("^--- scanner (.*) ---$").map_err(|e| format!("{}", e))?;
-    let name = regex.captures(lines[0]).and_then(|c| c.get(0)).map(|m| m.as_str().to_owned()).ok_or("No name found".to_owned())?;
+    let regex = Regex::new("^--- scanner (.*) ---$").map_err(|e| Error::invalid_state(format!("{}", e)))?;
+    let header = lines.first().filter(|l| !l.is_empty()).ok_or_else(|| Error::parse("Empty scanner").at_line(1))?;
+    let name = regex.captures(header).and_then(|c| c.get(0)).map(|m| m.as_str().to_owned()).ok_or_else(|| Error::parse("No name found").at_line(1))?;
 
-    let points: Vec<Point3D> = lines.iter().skip(1).map(|l| l.parse()).collect::<Result<Vec<Point3D>, String>>()?;
+    let points: Vec<Point3D> = lines.iter().enumerate().skip(1)
+        .map(|(i, l)| l.parse().map_err(|e: Error| e.at_line(i + 1)))
+        .collect::<Result<Vec<Point3D>, Error>>()?;
     Ok(Scanner { name, location: Point3D::default(), points })
 }
 
-fn parse_input(input: &str) -> Result<Vec<Scanner>, String> {
-    input.split("\n\n").map(|s| parse_scanner(s)).collect()
+fn parse_input(input: &str) -> Result<Vec<Scanner>, Error> {
+    // Scanners are separated by an empty line; errors are moved to the line of the scanner in the whole input.
+    let mut line = 0;
+    input.split("\n\n").map(|s| {
+        let scanner = parse_scanner(s).map_err(|e| e.offset(line, 0));
+        line += s.lines().count().max(1) + 1;
+        scanner
+    }).collect()
 }
 
 fn find_match(scanner: &Scanner, others: &Vec<Scanner>) -> Option<Scanner> {
@@ -126,22 +139,23 @@ fn find_match(scanner: &Scanner, others: &Vec<Scanner>) -> Option<Scanner> {
     None
 }
 
-fn map_all_beacons(scanners: &Vec<Scanner>) -> Vec<Point3D> {
-    let beacons: Vec<_> = map_scanners(scanners).iter().flat_map(|s| s.points.clone()).collect();
-    beacons.deduplicate()
+fn map_all_beacons(scanners: &Vec<Scanner>) -> Result<Vec<Point3D>, Error> {
+    let beacons: Vec<_> = map_scanners(scanners)?.iter().flat_map(|s| s.points.clone()).collect();
+    Ok(beacons.deduplicate())
 }
 
-fn map_scanners(scanners: &Vec<Scanner>) -> Vec<Scanner> {
+fn map_scanners(scanners: &Vec<Scanner>) -> Result<Vec<Scanner>, Error> {
     // The first scanner will be the anchor. let's match others, somehow.
-    let main = scanners[0].clone();
+    let main = scanners.first().ok_or_else(|| Error::MissingInput("There are no scanners".to_string()))?.clone();
 
-    let mut mapped = vec![scanners[0].clone()];
+    let mut mapped = vec![main.clone()];
     let mut to_map: Vec<_> = scanners.iter().cloned().filter(|s| main.ne(s)).collect();
 
     'main: while !to_map.is_empty() {
         let mut revmapped = mapped.clone();
         revmapped.reverse();
         for mapped_scanner in &revmapped {
+            cancel::check()?;
             if let Some(matched) = find_match(mapped_scanner, &to_map) {
                 to_map.retain(|v| v.name != matched.name);
                 mapped.push(matched);
@@ -149,17 +163,18 @@ fn map_scanners(scanners: &Vec<Scanner>) -> Vec<Scanner> {
             }
         }
 
-        panic!("Did not match any new scanner location 😱");
+        return Err(Error::no_solution("Did not match any new scanner location 😱"));
     }
 
-    mapped
+    Ok(mapped)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day19::{FacingDirection, find_match, map_all_beacons, parse_input, Scanner};
-    use crate::days::day19::FacingDirection::{XNeg, YNeg};
+    use crate::days::y2021::day19::{FacingDirection, find_match, map_all_beacons, parse_input, Scanner};
+    use crate::days::y2021::day19::FacingDirection::{XNeg, YNeg};
     use crate::util::geometry::Point3D;
+    use crate::error::Error;
 
     impl Scanner {
         fn translate(&self, by: &Point3D) -> Scanner {
@@ -195,13 +210,22 @@ mod tests {
         assert_eq!(second_match, Some(second_expected));
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input(""), Err(Error::parse("Empty scanner").at_line(1)));
+        assert_eq!(parse_input("--- scanner 0 ---\n1,2,3\n\n\n\n--- scanner 1 ---"), Err(Error::parse("Empty scanner").at_line(4)));
+        assert_eq!(parse_input("--- scanner 0 ---\n1,2,3\n\nscanner 1\n1,2,3"), Err(Error::parse("No name found").at_line(4)));
+        assert!(matches!(parse_input("--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n1,2,3\n1,x,3"), Err(Error::Parse(e)) if e.line == Some(6)));
+        assert_eq!(map_all_beacons(&vec![]), Err(Error::MissingInput("There are no scanners".to_string())));
+    }
+
     #[test]
     fn test_map_all_beacons() {
         let scanners = parse_input(EXAMPLE_INPUT).unwrap();
-        let mut beacons = map_all_beacons(&scanners);
+        let mut beacons = map_all_beacons(&scanners).unwrap();
         beacons.sort();
 
-        let mut expected = EXAMPLE_BEACONS.lines().map(|l| l.parse()).collect::<Result<Vec<Point3D>, String>>().unwrap();
+        let mut expected = EXAMPLE_BEACONS.lines().map(|l| l.parse()).collect::<Result<Vec<Point3D>, Error>>().unwrap();
         expected.sort();
 
         assert_eq!(beacons.len(), 79);
@@ -426,4 +450,4 @@ mod tests {
         1847,-1591,415\n\
         1889,-1729,1762\n\
         1994,-1805,1792";
-}
\ No newline at end of file
+}
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::geometry::{Point, SparseGrid};
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let mut puzzle = puzzle.clone();
+
+        puzzle.enhance();
+        puzzle.enhance();
+
+        Ok(puzzle.get_lit_pixels().into())
+    }
+
+    fn part2(puzzle: &Self::Input) -> Result<Answer, Error> {
+        let mut puzzle = puzzle.clone();
+
+        for _ in 0..50 {
+            puzzle.enhance();
+        }
+
+        Ok(puzzle.get_lit_pixels().into())
+    }
+}
+
+// The image is infinite: all pixels outside the part that was enhanced so far share the background value.
+type Image = SparseGrid<bool>;
+
+fn enhance_image(image: &Image, enhancement: &[bool; 512]) -> Image {
+    // The new background is either 000000000 (=0) or 111111111 = (511)
+    let background = if *image.background() { enhancement[511] } else { enhancement[0] };
+    let mut enhanced = SparseGrid::new(background);
+
+    // For all current pixels calculate the enhanced value
+    let mut bounds = image.bounds();
+    bounds.grow(1);
+
+    for y in bounds.y() {
+        for x in bounds.x() {
+            let value = get_value(image, &Point { x, y });
+            enhanced.set(Point { x, y }, enhancement[value]);
+        }
+    }
+
+    enhanced
+}
+
+fn get_value(image: &Image, pixel: &Point) -> usize {
+    let mut result = 0;
+
+    for y in pixel.y - 1..=pixel.y + 1 {
+        for x in pixel.x - 1..=pixel.x + 1 {
+            result <<= 1;
+            result += match image.get(&Point { x, y }) {
+                true => 1,
+                false => 0
+            };
+        }
+    }
+
+    result
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Puzzle {
+    enhancement: [bool; 512],
+    image: Image,
+}
+
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = data.lines().collect();
+
+        let first_line = lines.first().ok_or_else(|| Error::parse("Expected the enhancement data").at(1, 1))?;
+        let raw_enhancement: [char; 512] = first_line.chars().collect::<Vec<char>>().try_into()
+            .map_err(|e: Vec<char>| Error::parse(format!("Enhancement data wrong length: {}", e.len())).at(1, e.len().min(512) + 1))?;
+        let enhancement: [bool; 512] = raw_enhancement.map(|c| match c {
+            '#' => true,
+            _ => false
+        });
+
+        // The image starts after the enhancement data and an empty line.
+        let grid = lines.iter().enumerate().skip(2)
+            .map(|(y, l)| l.chars().enumerate().map(|(x, c)| match c {
+                '.' => Ok(false),
+                '#' => Ok(true),
+                _ => Err(Error::parse(format!("Invalid character '{}' in image", c)).at(y + 1, x + 1))
+            }).collect::<Result<Vec<bool>, Error>>())
+            .collect::<Result<Vec<Vec<bool>>, Error>>()?;
+
+        let mut image = SparseGrid::new(false);
+
+        for y in 0..grid.len() {
+            let line = &grid[y];
+            for x in 0..line.len() {
+                image.set(Point { x: x as isize, y: y as isize }, line[x]);
+            }
+        }
+
+        Ok(Puzzle { enhancement, image })
+    }
+}
+
+impl Puzzle {
+    fn enhance(&mut self) {
+        self.image = enhance_image(&self.image, &self.enhancement);
+    }
+
+    fn get_lit_pixels(&self) -> usize {
+        self.image.cells().filter(|(_, v)| **v).count()
+    }
+}
+
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Print, to make the image better understandable, we include 2 pixels around the map limits.
+        let mut bounds = self.image.bounds();
+        bounds.grow(2);
+
+        writeln!(f, "{}", self.image.window(bounds, |v| if *v { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::y2021::day20::Puzzle;
+    use crate::error::Error;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Puzzle>(), Err(Error::parse("Expected the enhancement data").at(1, 1)));
+        assert_eq!("#.#".parse::<Puzzle>(), Err(Error::parse("Enhancement data wrong length: 3").at(1, 4)));
+        let invalid_image = EXAMPLE_INPUT.replace("##..#", "##.x#");
+        assert_eq!(invalid_image.parse::<Puzzle>(), Err(Error::parse("Invalid character 'x' in image").at(5, 4)));
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let puzzle: Result<Puzzle, Error> = EXAMPLE_INPUT.parse();
+        assert!(puzzle.is_ok());
+        assert_eq!(format!("{}", puzzle.unwrap()), "\
+            .........\n\
+            .........\n\
+            ..#..#...\n\
+            ..#......\n\
+            ..##..#..\n\
+            ....#....\n\
+            ....###..\n\
+            .........\n\
+            .........\n\
+        ");
+    }
+
+    #[test]
+    fn test_enhance() {
+        let mut puzzle: Puzzle = EXAMPLE_INPUT.parse().unwrap();
+
+        puzzle.enhance();
+        assert_eq!(format!("{}", puzzle), "\
+            ...........\n\
+            ...........\n\
+            ...##.##...\n\
+            ..#..#.#...\n\
+            ..##.#..#..\n\
+            ..####..#..\n\
+            ...#..##...\n\
+            ....##..#..\n\
+            .....#.#...\n\
+            ...........\n\
+            ...........\n\
+        ");
+
+        puzzle.enhance();
+        assert_eq!(format!("{}", puzzle), "\
+            .............\n\
+            .............\n\
+            .........#...\n\
+            ...#..#.#....\n\
+            ..#.#...###..\n\
+            ..#...##.#...\n\
+            ..#.....#.#..\n\
+            ...#.#####...\n\
+            ....#.#####..\n\
+            .....##.##...\n\
+            ......###....\n\
+            .............\n\
+            .............\n\
+        ");
+
+        assert_eq!(puzzle.get_lit_pixels(), 35);
+
+        // Enhance until 50 times.
+        for _ in 0..48 {
+            puzzle.enhance();
+        }
+
+        assert_eq!(puzzle.get_lit_pixels(), 3351);
+    }
+
+    const EXAMPLE_INPUT: &str = "\
+        ..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..##\
+        #..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###\
+        .######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#.\
+        .#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#.....\
+        .#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#..\
+        ...####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.....\
+        ..##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#\n\
+        \n\
+        #..#.\n\
+        #....\n\
+        ##..#\n\
+        ..#..\n\
+        ..###";
+}
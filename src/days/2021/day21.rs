@@ -3,35 +3,39 @@ use std::ops::Mul;
 use std::str::FromStr;
 use num_bigint::BigUint;
 use num_traits::{One,Zero};
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number::parse_usize;
 
-pub const DAY21: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day21;
 
-fn puzzle1(input: &String) {
-    let mut game: Game = input.parse().unwrap();
+impl Solver for Day21 {
+    type Input = Game;
 
-    play_deterministic(&mut game);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-    let losing_score = if game.player_one_score >= 1000 { game.player_two_score } else { game.player_one_score };
-    let result = losing_score * game.dice_rolls;
+    fn part1(game: &Self::Input) -> Result<Answer, Error> {
+        let mut game = *game;
 
-    println!("Puzzle 1 answer: {}", result);
-}
+        play_deterministic(&mut game);
 
-fn puzzle2(input: &String) {
-    let game: Game = input.parse().unwrap();
+        let losing_score = if game.player_one_score >= 1000 { game.player_two_score } else { game.player_one_score };
+        let result = losing_score * game.dice_rolls;
 
-    let result = play_dirac(&game, 21);
+        Ok(result.into())
+    }
 
-    println!("Puzzle 2 answer: {}", result.num_universes_player_one.max(result.num_universes_player_two));
+    fn part2(game: &Self::Input) -> Result<Answer, Error> {
+        let result = play_dirac(game, 21);
+
+        Ok(result.num_universes_player_one.max(result.num_universes_player_two).into())
+    }
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
-struct Game {
+pub struct Game {
     player_one_position: usize,
     player_one_score: usize,
     player_two_position: usize,
@@ -58,16 +62,16 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: [&str; 2] = s.lines().collect::<Vec<&str>>().try_into().map_err(|v: Vec<&str>| format!("Incorrect number of lines: {}", v.len()))?;
+        let lines: [&str; 2] = s.lines().collect::<Vec<&str>>().try_into().map_err(|v: Vec<&str>| Error::parse(format!("Incorrect number of lines: {}", v.len())))?;
 
         if !lines[0].starts_with("Player 1 starting position: ") {
-            return Err(format!("Invalid first line: {}", lines[0]));
+            return Err(Error::parse(format!("Invalid first line: {}", lines[0])));
         }
         if !lines[1].starts_with("Player 2 starting position: ") {
-            return Err(format!("Invalid first line: {}", lines[0]));
+            return Err(Error::parse(format!("Invalid first line: {}", lines[0])));
         }
 
         let player_one_position = parse_usize(&lines[0][28..])?;
@@ -215,7 +219,7 @@ fn play_dirac(game: &Game, target_score: usize) -> GameResult {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day21::{Game, GameResult, play_deterministic, play_dirac};
+    use crate::days::y2021::day21::{Game, GameResult, play_deterministic, play_dirac};
 
     #[test]
     fn test_parse() {
@@ -250,4 +254,4 @@ mod tests {
         assert_eq!(num_universes_player_one, "444356092776315".parse().unwrap());
         assert_eq!(num_universes_player_two, "341960390180808".parse().unwrap());
     }
-}
\ No newline at end of file
+}
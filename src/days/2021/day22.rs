@@ -3,24 +3,26 @@ use std::str::FromStr;
 use regex::Regex;
 use num_bigint::{BigInt, BigUint};
 use num_traits::{One, Signed, Zero};
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
 use crate::util::number;
 
-pub const DAY22: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day22;
 
-fn puzzle1(input: &String) {
-    let puzzle: Puzzle = input.parse().unwrap();
+impl Solver for Day22 {
+    type Input = Puzzle;
 
-    println!("Puzzle 1 answer: {}", puzzle.count_initialize(Some(-50..=50)));
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-fn puzzle2(input: &String) {
-    let puzzle: Puzzle = input.parse().unwrap();
+    fn part1(puzzle: &Self::Input) -> Result<Answer, Error> {
+        Ok(puzzle.count_initialize(Some(-50..=50)).into())
+    }
 
-    println!("Puzzle 2 answer: {}", puzzle.count_initialize(None));
+    fn part2(puzzle: &Self::Input) -> Result<Answer, Error> {
+        Ok(puzzle.count_initialize(None).into())
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -89,18 +91,22 @@ impl Range3D {
 }
 
 impl FromStr for Range3D {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let regex = Regex::new("^x=(?P<xmin>-?\\d+)\\.\\.(?P<xmax>-?\\d+),y=(?P<ymin>-?\\d+)\\.\\.(?P<ymax>-?\\d+),z=(?P<zmin>-?\\d+)\\.\\.(?P<zmax>-?\\d+)$").map_err(|e| format!("{}", e))?;
+        let regex = Regex::new("^x=(?P<xmin>-?\\d+)\\.\\.(?P<xmax>-?\\d+),y=(?P<ymin>-?\\d+)\\.\\.(?P<ymax>-?\\d+),z=(?P<zmin>-?\\d+)\\.\\.(?P<zmax>-?\\d+)$").map_err(|e| Error::invalid_state(format!("{}", e)))?;
 
-        let captures = regex.captures(s).ok_or(format!("Could not match ranges"))?;
-        let xmin = number::parse_isize(captures.name("xmin").ok_or(format!("Missing xmin"))?.as_str())?;
-        let xmax = number::parse_isize(captures.name("xmax").ok_or(format!("Missing xmax"))?.as_str())?;
-        let ymin = number::parse_isize(captures.name("ymin").ok_or(format!("Missing ymin"))?.as_str())?;
-        let ymax = number::parse_isize(captures.name("ymax").ok_or(format!("Missing ymax"))?.as_str())?;
-        let zmin = number::parse_isize(captures.name("zmin").ok_or(format!("Missing zmin"))?.as_str())?;
-        let zmax = number::parse_isize(captures.name("zmax").ok_or(format!("Missing zmax"))?.as_str())?;
+        let captures = regex.captures(s).ok_or_else(|| Error::parse(format!("Could not match ranges in '{}'", s)).at(1, 1))?;
+        let parse = |name: &str| {
+            let value = captures.name(name).ok_or_else(|| Error::parse(format!("Missing {}", name)).at(1, 1))?;
+            number::parse_isize(value.as_str()).map_err(|e| e.at(1, value.start() + 1))
+        };
+        let xmin = parse("xmin")?;
+        let xmax = parse("xmax")?;
+        let ymin = parse("ymin")?;
+        let ymax = parse("ymax")?;
+        let zmin = parse("zmin")?;
+        let zmax = parse("zmax")?;
 
         Ok(Range3D {
             x: xmin..=xmax,
@@ -117,31 +123,32 @@ struct Command {
 }
 
 impl FromStr for Command {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: [&str; 2] = s.split(" ").collect::<Vec<_>>().try_into().map_err(|e: Vec<_>| format!("Wrong number of parts {:?}", e))?;
+        let parts: [&str; 2] = s.split(" ").collect::<Vec<_>>().try_into().map_err(|e: Vec<_>| Error::parse(format!("Wrong number of parts {:?}", e)).at(1, 1))?;
         let target = match parts[0] {
             "on" => Cube::On,
             "off" => Cube::Off,
-            _ => return Err(format!("Invalid command '{}'", parts[0]))
+            _ => return Err(Error::parse(format!("Invalid command '{}'", parts[0])).at(1, 1))
         };
-        let range: Range3D = parts[1].parse()?;
+        // The range starts after the command and a space.
+        let range: Range3D = parts[1].parse().map_err(|e: Error| e.offset(0, parts[0].len() + 1))?;
 
         Ok(Command { target, range })
     }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct Puzzle {
+pub struct Puzzle {
     commands: Vec<Command>,
 }
 
 impl FromStr for Puzzle {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let commands: Vec<Command> = s.lines().map(|l| l.parse()).collect::<Result<Vec<_>, String>>()?;
+        let commands: Vec<Command> = s.lines().enumerate().map(|(i, l)| l.parse().map_err(|e: Error| e.at_line(i + 1))).collect::<Result<Vec<_>, Error>>()?;
 
         Ok(Puzzle {
             commands,
@@ -193,7 +200,8 @@ mod tests {
     use std::ops::{RangeInclusive};
     use std::str::FromStr;
     use num_bigint::BigUint;
-    use crate::days::day22::{Command, Cube, overlap_range, Puzzle, Range3D};
+    use crate::days::y2021::day22::{Command, Cube, overlap_range, Puzzle, Range3D};
+    use crate::error::Error;
 
     const EXAMPLE_INPUT: &str = "\
         on x=-20..26,y=-36..17,z=-47..7\n\
@@ -221,7 +229,7 @@ mod tests {
 
     #[test]
     fn test_parse_puzzle() {
-        let puzzle: Result<Puzzle, String> = EXAMPLE_INPUT.parse();
+        let puzzle: Result<Puzzle, Error> = EXAMPLE_INPUT.parse();
 
         fn on(x: RangeInclusive<isize>, y: RangeInclusive<isize>, z: RangeInclusive<isize>) -> Command {
             Command { target: Cube::On, range: Range3D { x, y, z } }
@@ -258,6 +266,13 @@ mod tests {
         }))
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("on x=1..2,y=1..2,z=1..2\nof x=1..2,y=1..2,z=1..2".parse::<Puzzle>(), Err(Error::parse("Invalid command 'of'").at(2, 1)));
+        assert_eq!("on x=1..2,y=1..2,z=1..2\noff x=1..2,y=1.2,z=1..2".parse::<Puzzle>(), Err(Error::parse("Could not match ranges in 'x=1..2,y=1.2,z=1..2'").at(2, 5)));
+        assert_eq!("on x=1..2,y=1..99999999999999999999,z=1..2".parse::<Puzzle>().map_err(|e| e.to_string()), Err("Parse error at line 1, column 16: Invalid isize '99999999999999999999': number too large to fit in target type".to_string()));
+    }
+
     #[test]
     fn test_capped() {
         let range = Range3D {
@@ -385,4 +400,4 @@ mod tests {
         off x=-70369..-16548,y=22648..78696,z=-1892..86821\n\
         on x=-53470..21291,y=-120233..-33476,z=-44150..38147\n\
         off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
-}
\ No newline at end of file
+}
@@ -1,43 +1,48 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
 use std::str::FromStr;
 use regex::Regex;
-use crate::days::Day;
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::search;
 
-pub const DAY23: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day23;
 
-fn puzzle1(input: &String) {
-    let state: State = input.parse().unwrap();
-    
-    let result = find_least_energy_sort(&state);
-    
-    println!("Puzzle 1 answer: {}", result.unwrap().used_energy);
-}
+impl Solver for Day23 {
+    type Input = State;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
 
-fn puzzle2(input: &String) {
-    let mut state: State = input.parse().unwrap();
-
-    // Run modifications according to puzzle 2
-    state.room_size = 4;
-    state.room_a[3] = state.room_a[1];
-    state.room_a[1] = Some(Amphipod::D);
-    state.room_a[2] = Some(Amphipod::D);
-    state.room_b[3] = state.room_b[1];
-    state.room_b[1] = Some(Amphipod::C);
-    state.room_b[2] = Some(Amphipod::B);
-    state.room_c[3] = state.room_c[1];
-    state.room_c[1] = Some(Amphipod::B);
-    state.room_c[2] = Some(Amphipod::A);
-    state.room_d[3] = state.room_d[1];
-    state.room_d[1] = Some(Amphipod::A);
-    state.room_d[2] = Some(Amphipod::C);
-
-    let result = find_least_energy_sort(&state);
-
-    println!("Puzzle 2 answer: {}", result.unwrap().used_energy);
+    fn part1(state: &Self::Input) -> Result<Answer, Error> {
+        let used_energy = find_least_energy_sort(state)?
+            .ok_or_else(|| Error::no_solution("Could not sort the amphipods"))?;
+
+        Ok(used_energy.into())
+    }
+
+    fn part2(state: &Self::Input) -> Result<Answer, Error> {
+        let mut state = state.clone();
+
+        // Run modifications according to puzzle 2
+        state.room_size = 4;
+        state.room_a[3] = state.room_a[1];
+        state.room_a[1] = Some(Amphipod::D);
+        state.room_a[2] = Some(Amphipod::D);
+        state.room_b[3] = state.room_b[1];
+        state.room_b[1] = Some(Amphipod::C);
+        state.room_b[2] = Some(Amphipod::B);
+        state.room_c[3] = state.room_c[1];
+        state.room_c[1] = Some(Amphipod::B);
+        state.room_c[2] = Some(Amphipod::A);
+        state.room_d[3] = state.room_d[1];
+        state.room_d[1] = Some(Amphipod::A);
+        state.room_d[2] = Some(Amphipod::C);
+
+        let used_energy = find_least_energy_sort(&state)?
+            .ok_or_else(|| Error::no_solution("Could not sort the amphipods"))?;
+
+        Ok(used_energy.into())
+    }
 }
 
 /*
@@ -78,7 +83,7 @@ enum Amphipod {
 }
 
 impl FromStr for Amphipod {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -86,7 +91,7 @@ impl FromStr for Amphipod {
             "B" => Ok(Amphipod::B),
             "C" => Ok(Amphipod::C),
             "D" => Ok(Amphipod::D),
-            _ => Err(format!("Invalid amphipod: {}", s))
+            _ => Err(Error::parse(format!("Invalid amphipod: {}", s)))
         }
     }
 }
@@ -122,18 +127,17 @@ impl Amphipod {
 }
 
 #[derive(Eq, PartialEq, Clone, Hash, Default)]
-struct State {
+pub struct State {
     hallway: [Option<Amphipod>; 7],
     room_a: [Option<Amphipod>; 4],
     room_b: [Option<Amphipod>; 4],
     room_c: [Option<Amphipod>; 4],
     room_d: [Option<Amphipod>; 4],
     room_size: usize,
-    used_energy: usize,
 }
 
 impl FromStr for State {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<_> = s.lines().collect();
@@ -141,18 +145,18 @@ impl FromStr for State {
             lines[0].trim().ne("#############") ||
             lines[1].trim().ne("#...........#") ||
             lines[4].trim_end().ne("  #########") {
-            return Err(String::from("Invalid format"));
+            return Err(Error::parse("Invalid format"));
         }
 
-        let line2regex = Regex::new("^###(?P<A>[A-D])#(?P<B>[A-D])#(?P<C>[A-D])#(?P<D>[A-D])###\\s*$").map_err(|e| format!("{}", e))?;
-        let line3regex = Regex::new("^  #(?P<A>[A-D])#(?P<B>[A-D])#(?P<C>[A-D])#(?P<D>[A-D])#\\s*$").map_err(|e| format!("{}", e))?;
+        let line2regex = Regex::new("^###(?P<A>[A-D])#(?P<B>[A-D])#(?P<C>[A-D])#(?P<D>[A-D])###\\s*$").map_err(|e| Error::invalid_state(format!("{}", e)))?;
+        let line3regex = Regex::new("^  #(?P<A>[A-D])#(?P<B>[A-D])#(?P<C>[A-D])#(?P<D>[A-D])#\\s*$").map_err(|e| Error::invalid_state(format!("{}", e)))?;
 
-        fn get_amphipods(regex: Regex, line: &str) -> Result<(Amphipod, Amphipod, Amphipod, Amphipod), String> {
-            let line_caps = regex.captures(line).ok_or(format!("Could not match line '{}' with '{}'", line, regex))?;
-            let a: Amphipod = line_caps.name("A").ok_or(String::from("Missing <A>"))?.as_str().parse()?;
-            let b: Amphipod = line_caps.name("B").ok_or(String::from("Missing <B>"))?.as_str().parse()?;
-            let c: Amphipod = line_caps.name("C").ok_or(String::from("Missing <C>"))?.as_str().parse()?;
-            let d: Amphipod = line_caps.name("D").ok_or(String::from("Missing <D>"))?.as_str().parse()?;
+        fn get_amphipods(regex: Regex, line: &str) -> Result<(Amphipod, Amphipod, Amphipod, Amphipod), Error> {
+            let line_caps = regex.captures(line).ok_or_else(|| Error::parse(format!("Could not match line '{}' with '{}'", line, regex)))?;
+            let a: Amphipod = line_caps.name("A").ok_or_else(|| Error::parse("Missing <A>"))?.as_str().parse()?;
+            let b: Amphipod = line_caps.name("B").ok_or_else(|| Error::parse("Missing <B>"))?.as_str().parse()?;
+            let c: Amphipod = line_caps.name("C").ok_or_else(|| Error::parse("Missing <C>"))?.as_str().parse()?;
+            let d: Amphipod = line_caps.name("D").ok_or_else(|| Error::parse("Missing <D>"))?.as_str().parse()?;
             Ok((a, b, c, d))
         }
 
@@ -210,18 +214,6 @@ impl std::fmt::Debug for State {
     }
 }
 
-impl std::cmp::Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.used_energy.cmp(&self.used_energy)
-    }
-}
-
-impl std::cmp::PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 impl State {
     fn get_room_state(&self, room: Amphipod) -> [Option<Amphipod>; 4] {
         match room {
@@ -302,39 +294,40 @@ fn can_move_into_room(state: &State, index: usize) -> bool {
     room_state.iter().all(|s| s.is_none() || Some(target).eq(s))
 }
 
-fn find_least_energy_sort(initial_state: &State) -> Option<State> {
-    // Build a shortest path with stack.
-    // - Order by the used energy
+fn find_least_energy_sort(initial_state: &State) -> Result<Option<usize>, Error> {
+    // Build a shortest path with Dijkstra.
+    // - The cost of a move is the energy it uses
     // - From each state, derive possible new states by:
     //   - Moving from a wrong room into reachable free spots in the hallway
     //   - Moving from the hallway to a reachable correct room (= empty or already filled with a right amphipod)
     // - Finish when finding a finished state, should be the cheapest by the algorithm
     // - Discard state when no moves possible
 
-    fn create_move_out_states(state: &State) -> Vec<State> {
-        fn create_state(state: &State, from: Amphipod) -> Vec<State> {
+    fn create_move_out_states(state: &State) -> Vec<(State, usize)> {
+        fn create_state(state: &State, from: Amphipod) -> Vec<(State, usize)> {
             let mut new_states = vec![];
 
             for idx in get_hallway_options(from, &state.hallway) {
                 let mut new_state = state.clone();
+                let mut used_energy = 0;
                 let from_room = new_state.get_room_state_mut(from);
                 
                 for ridx in 0..state.room_size {
                     if let Some(p) = from_room[ridx] {
                         from_room[ridx] = None;
                         new_state.hallway[idx] = Some(p);
-                        new_state.used_energy += p.get_cost_between(from, ridx, idx);
+                        used_energy += p.get_cost_between(from, ridx, idx);
                         break;
                     }
                 }
                 
-                new_states.push(new_state);
+                new_states.push((new_state, used_energy));
             }
 
             new_states
         }
 
-        fn get_states_from_room(state: &State, room: Amphipod) -> Vec<State> {
+        fn get_states_from_room(state: &State, room: Amphipod) -> Vec<(State, usize)> {
             let room_state = state.get_room_state(room);
             
             for r_idx in 0..state.room_size {
@@ -360,7 +353,7 @@ fn find_least_energy_sort(initial_state: &State) -> Option<State> {
             .collect()
     }
     
-    fn create_move_in_states(state: &State) -> Vec<State> {
+    fn create_move_in_states(state: &State) -> Vec<(State, usize)> {
         // For each amphipod in the hallway, check if there is an option to move to it's destination room.
         let mut new_states = vec![];
         
@@ -368,16 +361,17 @@ fn find_least_energy_sort(initial_state: &State) -> Option<State> {
             if let Some(p) = state.hallway[idx] {
                 if can_move_into_room(state, idx) {
                     let mut new_state = state.clone();
+                    let mut used_energy = 0;
                     new_state.hallway[idx] = None;
                     let room_state = new_state.get_room_state_mut(p);
                     for ridx in (0..state.room_size).rev() {
                         if room_state[ridx] == None {
                             room_state[ridx] = Some(p);
-                            new_state.used_energy += p.get_cost_between(p, ridx, idx);
+                            used_energy += p.get_cost_between(p, ridx, idx);
                             break;
                         }
                     }
-                    new_states.push(new_state);
+                    new_states.push((new_state, used_energy));
                 }
             }
         }
@@ -385,7 +379,7 @@ fn find_least_energy_sort(initial_state: &State) -> Option<State> {
         new_states
     }
 
-    fn create_next_states(state: &State) -> Vec<State> {
+    fn create_next_states(state: &State) -> Vec<(State, usize)> {
         // 1. Check the top-most rooms; if there is a wrong amphipod there or below it,
         //    generate states for it moving to every reachable hallway spot.
         // 2. Do the same for the free bottom rooms.
@@ -396,33 +390,18 @@ fn find_least_energy_sort(initial_state: &State) -> Option<State> {
         move_out_states.into_iter().chain(move_in_states.into_iter()).collect()
     }
 
-    let mut queue = BinaryHeap::new();
-    let mut seen_states = HashMap::<State, usize>::new();
-    queue.push(initial_state.clone());
-
-    while let Some(state) = queue.pop() {
-        if state.is_finished() {
-            return Some(state)
-        }
-        
-        if let Some(used_energy) = seen_states.get(&state) {
-            if state.used_energy.ge(used_energy) {
-                continue; // We've been here, but quicker.
-            }
-        }
-        
-        seen_states.insert(state.clone(), state.used_energy.clone());
+    let result = search::dijkstra(initial_state.clone(), create_next_states, |state| state.is_finished())?;
 
-        create_next_states(&state).into_iter().for_each(|s| queue.push(s));
-    }
-    
-    None
+    Ok(result.map(|r| r.cost))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day23::Amphipod::{A, B, C, D};
-    use crate::days::day23::{Amphipod, find_least_energy_sort, get_hallway_options, State};
+    use std::time::Duration;
+    use crate::days::y2021::day23::Amphipod::{A, B, C, D};
+    use crate::days::y2021::day23::{Amphipod, find_least_energy_sort, get_hallway_options, State};
+    use crate::error::Error;
+    use crate::util::cancel::{run_with, CancellationToken};
 
     const EXAMPLE_INPUT: &str = "\
 #############
@@ -441,7 +420,6 @@ mod tests {
             room_c: [Some(B), Some(C), None, None],
             room_d: [Some(D), Some(A), None, None],
             room_size: 2,
-            used_energy: 0,
         });
 
         assert_eq!(format!("{}", state), EXAMPLE_INPUT);
@@ -494,10 +472,9 @@ mod tests {
     fn test_find_least_energy_sort() {
         let state: State = EXAMPLE_INPUT.parse().unwrap();
         
-        let result = find_least_energy_sort(&state);
+        let result = find_least_energy_sort(&state).unwrap();
         
-        assert!(result.is_some());
-        assert_eq!(result.unwrap().used_energy, 12521);
+        assert_eq!(result, Some(12521));
     }
     
     #[test]
@@ -518,8 +495,15 @@ mod tests {
         state.room_d[1] = Some(A);
         state.room_d[2] = Some(C);
         
-        let result = find_least_energy_sort(&state);
-        assert!(result.is_some());
-        assert_eq!(result.unwrap().used_energy, 44169);
+        let result = find_least_energy_sort(&state).unwrap();
+        assert_eq!(result, Some(44169));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_find_least_energy_sort_timeout() {
+        let state: State = EXAMPLE_INPUT.parse().unwrap();
+
+        let result = run_with(Some(CancellationToken::with_timeout(Duration::ZERO)), || find_least_energy_sort(&state));
+        assert!(matches!(result, Err(Error::TimedOut(_))));
+    }
+}
@@ -1,26 +1,35 @@
 use std::str::FromStr;
-use crate::days::Day;
-use crate::util::geometry::{Grid, Point};
+use crate::days::{Answer, Solver};
+use crate::error::Error;
+use crate::util::geometry::{Direction, Grid, Point, Topology};
 
-pub const DAY25: Day = Day {
-    puzzle1,
-    puzzle2,
-};
+pub struct Day25;
 
-fn puzzle1(input: &String) {
-    let mut grid: Grid<Snail> = input.parse().unwrap();
-    
-    let result = grid.cycle_till_stacked();
-    
-    println!("Puzzle 1 answer: {}", result);
-}
+impl Solver for Day25 {
+    type Input = Grid<Snail>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        // Snails moving off the bottom or right edge reappear at the top or left edge.
+        let mut grid: Grid<Snail> = input.parse()?;
+        grid.topology = Topology::Toroidal;
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, Error> {
+        let mut grid = grid.clone();
+
+        let result = grid.cycle_till_stacked();
+
+        Ok(result.into())
+    }
 
-fn puzzle2(_input: &String) {
-    println!("Puzzle 2 is a freebie, as always :D");
+    fn part2(_input: &Self::Input) -> Result<Answer, Error> {
+        Ok(Answer::Text("Puzzle 2 is a freebie, as always :D".to_string()))
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
-enum Snail {
+pub enum Snail {
     None,
     East,
     South,
@@ -37,14 +46,14 @@ impl std::fmt::Display for Snail {
 }
 
 impl FromStr for Snail {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "." => Ok(Snail::None),
             ">" => Ok(Snail::East),
             "v" => Ok(Snail::South),
-            _ => Err(format!("Not a snail: '{}'", s))
+            _ => Err(Error::parse(format!("Not a snail: '{}'", s)))
         }
     }
 }
@@ -57,32 +66,15 @@ impl Default for Snail {
 
 impl Snail {
     fn get_next_position(&self, grid: &Grid<Snail>, current: &Point) -> Option<Point> {
-        let mut target = current.clone();
-
-        match self {
-            Snail::None => { return None }
-            Snail::East => {
-                target.x += 1;
-                if target.x == grid.bounds.right() {
-                    target.x = grid.bounds.left;
-                }
-            }
-            Snail::South => {
-                target.y += 1;
-                if target.y == grid.bounds.bottom() {
-                    target.y = grid.bounds.top;
-                }
-            }
-        }
-
-        if let Some(s) = grid.get(&target) {
-            if s == Snail::None {
-                Some(target)
-            } else {
-                None
-            }
-        } else {
-            Some(target)
+        let target = match self {
+            Snail::None => return None,
+            Snail::East => grid.step(current, Direction::Right)?,
+            Snail::South => grid.step(current, Direction::Down)?,
+        };
+
+        match grid.get(&target) {
+            Some(Snail::None) | None => Some(target),
+            Some(_) => None
         }
     }
 }
@@ -123,8 +115,8 @@ impl Grid<Snail> {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day25::Snail;
-    use crate::util::geometry::Grid;
+    use crate::days::Solver;
+    use crate::days::y2021::day25::Day25;
 
     const EXAMPLE_INPUT: &str = "\
         v...>>.vv>\n\
@@ -139,7 +131,7 @@ mod tests {
 
     #[test]
     fn test_cycle() {
-        let mut grid: Grid<Snail> = EXAMPLE_INPUT.parse().unwrap();
+        let mut grid = Day25::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(grid.cycle(), true);
         assert_eq!(format!("{}", grid), "\
             ....>.>v.>\n\
@@ -166,7 +158,7 @@ mod tests {
     
     #[test]
     fn test_cycle_till_stacked() {
-        let mut grid: Grid<Snail> = EXAMPLE_INPUT.parse().unwrap();
+        let mut grid = Day25::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(grid.cycle_till_stacked(), 58);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,170 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::Duration;
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ParseError {
+    pub message: String,
+    // 1-based line and column of the offending input, when known.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Error {
+    // The input could not be parsed.
+    Parse(ParseError),
+    // A puzzle ended up in a state it cannot continue from.
+    InvalidState(String),
+    // A puzzle ran to completion without finding an answer.
+    NoSolution(String),
+    // The input could not be read.
+    MissingInput(String),
+    // A puzzle was cancelled, as it did not finish within the given time.
+    TimedOut(Duration),
+    // A puzzle panicked, with the message it panicked with.
+    Panicked(String),
+    // There is no such day: the days of a year go from 1 to 25.
+    InvalidDay(i32),
+    // The given day of the given year has no solver (yet).
+    NotImplemented { year: i32, day: i32 },
+}
+
+impl Error {
+    pub fn parse<S: Into<String>>(message: S) -> Self {
+        Error::Parse(ParseError { message: message.into(), line: None, column: None })
+    }
+
+    pub fn invalid_state<S: Into<String>>(message: S) -> Self {
+        Error::InvalidState(message.into())
+    }
+
+    pub fn no_solution<S: Into<String>>(message: S) -> Self {
+        Error::NoSolution(message.into())
+    }
+
+    // Sets the location of a parse error, other errors are returned as-is.
+    pub fn at(self, line: usize, column: usize) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(ParseError { line: Some(line), column: Some(column), ..e }),
+            other => other
+        }
+    }
+
+    // Sets the line of a parse error, keeping the column if it was already known.
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(ParseError { line: Some(line), ..e }),
+            other => other
+        }
+    }
+
+    // Moves the location of a parse error, for errors that came from parsing only a part of the input.
+    pub fn offset(self, lines: usize, columns: usize) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(ParseError { line: e.line.map(|l| l + lines), column: e.column.map(|c| c + columns), ..e }),
+            other => other
+        }
+    }
+
+    // Formats the error, followed by the offending line of the input and a caret pointing at the column when known.
+    pub fn render(&self, input: &str) -> String {
+        let (line, column) = match self {
+            Error::Parse(ParseError { line: Some(line), column, .. }) => (*line, *column),
+            _ => return self.to_string()
+        };
+        let text = match line.checked_sub(1).and_then(|l| input.lines().nth(l)) {
+            Some(text) => text,
+            None => return self.to_string()
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        let mut lines = vec![self.to_string(), format!("{} | {}", line, text)];
+        if let Some(column) = column {
+            lines.push(format!("{} | {}^", gutter, " ".repeat(column.saturating_sub(1))));
+        }
+        lines.join("\n")
+    }
+}
+
+// Runs `f`, and returns Error::Panicked if it panics, so a bug in one puzzle does not end a run of all of them.
+pub fn catch_panic<R, F>(f: F) -> Result<R, Error> where F: FnOnce() -> Result<R, Error>
+{
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(Error::Panicked(panic_message(payload.as_ref()))))
+}
+
+// The message of a caught panic: panics with a message carry it as either a &str or a String.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "(no message)".to_string()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(ParseError { message, line: Some(line), column: Some(column) }) => write!(f, "Parse error at line {}, column {}: {}", line, column, message),
+            Error::Parse(ParseError { message, line: Some(line), column: None }) => write!(f, "Parse error at line {}: {}", line, message),
+            Error::Parse(ParseError { message, .. }) => write!(f, "Parse error: {}", message),
+            Error::InvalidState(message) => write!(f, "Invalid state: {}", message),
+            Error::NoSolution(message) => write!(f, "No solution: {}", message),
+            Error::MissingInput(message) => write!(f, "Missing input: {}", message),
+            Error::TimedOut(timeout) => write!(f, "Timed out after {:?}", timeout),
+            Error::Panicked(message) => write!(f, "Panicked: {}", message),
+            Error::InvalidDay(day) => write!(f, "Invalid day {}, expected a day from 1 to 25", day),
+            Error::NotImplemented { year, day } => write!(f, "No implementation yet for day {} of {}", day, year),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{catch_panic, Error, ParseError};
+
+    #[test]
+    fn test_location() {
+        assert_eq!(Error::parse("oops").at(3, 5), Error::Parse(ParseError { message: "oops".to_string(), line: Some(3), column: Some(5) }));
+        assert_eq!(Error::parse("oops").at(3, 5).at_line(7), Error::Parse(ParseError { message: "oops".to_string(), line: Some(7), column: Some(5) }));
+        assert_eq!(Error::invalid_state("stuck").at(1, 1), Error::InvalidState("stuck".to_string()));
+    }
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(Error::parse("oops").at(3, 5).offset(2, 4), Error::parse("oops").at(5, 9));
+        assert_eq!(Error::parse("oops").at_line(3).offset(2, 4), Error::parse("oops").at_line(5));
+        assert_eq!(Error::parse("oops").offset(2, 4), Error::parse("oops"));
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "1\n2\nx3\n4";
+        assert_eq!(Error::parse("oops").at(3, 1).render(input), "Parse error at line 3, column 1: oops\n3 | x3\n  | ^");
+        assert_eq!(Error::parse("oops").at(3, 2).render(input), "Parse error at line 3, column 2: oops\n3 | x3\n  |  ^");
+        assert_eq!(Error::parse("oops").at_line(2).render(input), "Parse error at line 2: oops\n2 | 2");
+        assert_eq!(Error::parse("oops").at_line(12).render(input), "Parse error at line 12: oops");
+        assert_eq!(Error::no_solution("none").render(input), "No solution: none");
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format!("{}", Error::parse("oops")), "Parse error: oops");
+        assert_eq!(format!("{}", Error::parse("oops").at_line(2)), "Parse error at line 2: oops");
+        assert_eq!(format!("{}", Error::parse("oops").at(2, 4)), "Parse error at line 2, column 4: oops");
+        assert_eq!(format!("{}", Error::no_solution("none")), "No solution: none");
+        assert_eq!(format!("{}", Error::Panicked("oops".to_string())), "Panicked: oops");
+        assert_eq!(format!("{}", Error::InvalidDay(26)), "Invalid day 26, expected a day from 1 to 25");
+        assert_eq!(format!("{}", Error::NotImplemented { year: 2020, day: 3 }), "No implementation yet for day 3 of 2020");
+        assert_eq!(format!("{}", Error::MissingInput("gone".to_string())), "Missing input: gone");
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(42)), Ok(42));
+        assert_eq!(catch_panic(|| Err::<i32, Error>(Error::no_solution("none"))), Err(Error::no_solution("none")));
+        assert_eq!(catch_panic::<i32, _>(|| panic!("oops")), Err(Error::Panicked("oops".to_string())));
+        assert_eq!(catch_panic::<i32, _>(|| panic!("oops at {}", 3)), Err(Error::Panicked("oops at 3".to_string())));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod days;
+pub mod error;
+pub mod util;
+
+pub use days::{available_days, available_years, get_day, Answer, Day, ParsedInput, Puzzle, Renderer, Solver};
+pub use error::Error;
@@ -1,69 +1,487 @@
-mod days;
-mod util;
+mod cli;
+mod report;
 
 use std::env::args;
-use days::{get_day, Day};
-use util::input::{read_input};
-use util::number::{parse_i32};
+use std::fs::write;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+use advent_of_code_2021::{available_days, available_years, get_day, Answer, Day, Error};
+use advent_of_code_2021::util::answers::{read_answers, Verification};
+use advent_of_code_2021::util::bench::{sample, Statistics};
+use advent_of_code_2021::util::cancel::{run_with, CancellationToken};
+use advent_of_code_2021::util::create_day::{create_day, plan_day, render_plan};
+use advent_of_code_2021::util::import::{import_input, Import};
+use advent_of_code_2021::util::input::{read_input, InputSource};
+use advent_of_code_2021::util::number::{parse_i32, parse_usize};
+use advent_of_code_2021::util::parallel::{default_jobs, for_each_ordered};
+use advent_of_code_2021::util::render::{to_ppm, to_text};
+use cli::{Arg, Command, Matches, Opt, Parsed};
+use report::DayReport;
 
-fn print_usage()
+const YEAR_ARG: Arg = Arg { name: "year", description: "The year of the puzzle, e.g. 2021.", required: true };
+const DAY_ARG: Arg = Arg { name: "day", description: "The day number (1-25).", required: true };
+const PART_OPT: Opt = Opt { name: "part", value: Some("1|2"), description: "Only run the given puzzle of the day." };
+const INPUT_OPT: Opt = Opt { name: "input", value: Some("path"), description: "Read the puzzle input from the given file ('-' for stdin) instead of resources/<year>/dayNN.txt." };
+const INPUT_TEXT_OPT: Opt = Opt { name: "input-text", value: Some("text"), description: "Use the given text as puzzle input, with '\\n' for line breaks." };
+const FORMAT_OPT: Opt = Opt { name: "format", value: Some("text|json"), description: "Print the results as text (default), or as JSON." };
+const TIMEOUT_OPT: Opt = Opt { name: "timeout", value: Some("seconds"), description: "Cancel a puzzle that runs longer than this, and report it as timed out." };
+const EXAMPLE_OPT: Opt = Opt { name: "example", value: Some("name"), description: "Use the named example from resources/<year>/examples as puzzle input ('default' for dayNN.txt)." };
+
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "day",
+        description: "Run the puzzles for the given day.",
+        args: &[YEAR_ARG, DAY_ARG],
+        options: &[PART_OPT, INPUT_OPT, INPUT_TEXT_OPT, EXAMPLE_OPT, FORMAT_OPT, TIMEOUT_OPT],
+    },
+    Command {
+        name: "all",
+        description: "Run the puzzles for all days, and show their answers and timings.",
+        args: &[Arg { name: "year", description: "The year to run, all years if not given.", required: false }],
+        options: &[
+            FORMAT_OPT,
+            TIMEOUT_OPT,
+            Opt { name: "jobs", value: Some("n"), description: "The number of days to run in parallel (default: one per core)." },
+        ],
+    },
+    Command {
+        name: "bench",
+        description: "Run the puzzles for the given day repeatedly, and show timing statistics.",
+        args: &[YEAR_ARG, DAY_ARG],
+        options: &[
+            Opt { name: "iterations", value: Some("n"), description: "The number of runs per puzzle (default: 10)." },
+            PART_OPT,
+            INPUT_OPT,
+            INPUT_TEXT_OPT,
+            EXAMPLE_OPT,
+        ],
+    },
+    Command {
+        name: "verify",
+        description: "Check the puzzle answers against resources/<year>/answers.json.",
+        args: &[
+            Arg { name: "year", description: "The year to verify, all years if not given.", required: false },
+            Arg { name: "day", description: "The day number to verify, all days of the year if not given.", required: false },
+        ],
+        options: &[],
+    },
+    Command {
+        name: "add",
+        description: "Add the source, input and example files for a new day.",
+        args: &[YEAR_ARG, DAY_ARG],
+        options: &[
+            Opt { name: "template", value: Some("name"), description: "The template from resources/templates to start from, e.g. grid or parse-lines (default: default)." },
+            Opt { name: "title", value: Some("text"), description: "The title of the puzzle, added to the top of the source file." },
+            Opt { name: "dry-run", value: None, description: "Show the files that would be created, without writing them." },
+        ],
+    },
+    Command {
+        name: "import",
+        description: "Copy a puzzle input into resources/<year>/dayNN.txt, and record its checksum.",
+        args: &[YEAR_ARG, DAY_ARG, Arg { name: "file", description: "The file to import ('-' for stdin).", required: true }],
+        options: &[Opt { name: "force", value: None, description: "Overwrite an existing input that differs from the imported one." }],
+    },
+    Command {
+        name: "render",
+        description: "Show how a puzzle was solved, for the days that support it, e.g. the path found on day 15 of 2021.",
+        args: &[YEAR_ARG, DAY_ARG],
+        options: &[
+            Opt { name: "part", value: Some("1|2"), description: "The puzzle to render (default: 1)." },
+            INPUT_OPT,
+            INPUT_TEXT_OPT,
+            EXAMPLE_OPT,
+            Opt { name: "output", value: Some("path"), description: "Write the picture to the given file as a PPM image, instead of printing it." },
+        ],
+    },
+];
+
+fn main() {
+    let a: Vec<String> = args().skip(1).collect();
+
+    let matches = match cli::parse(COMMANDS, &a) {
+        Ok(Parsed::Run(matches)) => matches,
+        Ok(Parsed::Help(Some(command))) => {
+            println!("{}", cli::command_usage(command));
+            return;
+        }
+        Ok(Parsed::Help(None)) => {
+            println!("{}", cli::usage(COMMANDS));
+            return;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::usage(COMMANDS));
+            std::process::exit(2);
+        }
+    };
+
+    let result = match matches.command {
+        "day" => run_day(&matches),
+        "all" => run_all(&matches),
+        "bench" => bench_day(&matches),
+        "verify" => verify(&matches),
+        "add" => add_day(&matches),
+        "import" => import(&matches),
+        "render" => render_day(&matches),
+        _ => unreachable!("Command '{}' is defined but not handled", matches.command)
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+fn parse_part(matches: &Matches) -> Result<Option<i32>, String> {
+    match matches.option("part") {
+        None => Ok(None),
+        Some("1") => Ok(Some(1)),
+        Some("2") => Ok(Some(2)),
+        Some(other) => Err(format!("Invalid part '{}', expected 1 or 2", other))
+    }
+}
+
+#[derive(Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn parse_format(matches: &Matches) -> Result<Format, String> {
+    match matches.option("format") {
+        None | Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some(other) => Err(format!("Invalid format '{}', expected text or json", other))
+    }
+}
+
+fn parse_timeout(matches: &Matches) -> Result<Option<Duration>, String> {
+    // Durations can't be negative or larger than u64::MAX seconds.
+    match matches.option("timeout").map(|v| v.parse::<f64>().ok().filter(|s| *s > 0.0).map(Duration::try_from_secs_f64)) {
+        None => Ok(None),
+        Some(Some(Ok(timeout))) => Ok(Some(timeout)),
+        Some(_) => Err(format!("Invalid timeout '{}', expected a positive number of seconds", matches.option("timeout").unwrap_or_default()))
+    }
+}
+
+// Parses the given optional argument as a number, e.g. the year or day.
+fn parse_number_arg(matches: &Matches, name: &str) -> Result<Option<i32>, String> {
+    matches.arg(name).map(|v| parse_i32(v).map_err(|e| e.to_string())).transpose()
+}
+
+fn parse_year_and_day(matches: &Matches) -> Result<(i32, i32), String> {
+    match (parse_number_arg(matches, "year")?, parse_number_arg(matches, "day")?) {
+        (Some(year), Some(day)) => Ok((year, day)),
+        _ => Err("Expected both a year and a day".to_string())
+    }
+}
+
+fn load_day(matches: &Matches) -> Result<(Day, String), String> {
+    let (day, source) = find_day(matches)?;
+    Ok((day, source.read().map_err(|e| e.to_string())?))
+}
+
+// The day and the source of its input, as given by the arguments. The input itself is not read yet.
+fn find_day(matches: &Matches) -> Result<(Day, InputSource), String> {
+    let (year, day_number) = parse_year_and_day(matches)?;
+    let day = get_day(year, day_number).map_err(|e| e.to_string())?;
+    let source = match (matches.option("input"), matches.option("input-text"), matches.option("example")) {
+        (Some(path), None, None) => InputSource::from_path(path),
+        (None, Some(text), None) => InputSource::Inline(text.replace("\\n", "\n")),
+        (None, None, Some(name)) => InputSource::example(year, day_number, name),
+        (None, None, None) => InputSource::Day(year, day_number),
+        _ => return Err("Use only one of --input, --input-text and --example".to_string())
+    };
+    Ok((day, source))
+}
+
+fn run_day(matches: &Matches) -> Result<(), String>
 {
-    eprintln!("
-Usage: cargo run <command> [<command_arg>, ...]
+    let part = parse_part(matches)?;
+    let format = parse_format(matches)?;
+    let timeout = parse_timeout(matches)?;
 
-Commands:
-    day <day number> - run the puzzles for the given day.
-");
+    if format == Format::Json {
+        // An input that cannot be read is reported in the JSON as well, like `all` does.
+        let (year, day_number) = parse_year_and_day(matches)?;
+        let (day, source) = find_day(matches)?;
+        let report = match source.read() {
+            Ok(input) => DayReport::run(year, day_number, &day, &input, part, timeout),
+            Err(err) => DayReport::failed(year, day_number, err)
+        };
+        println!("{}", serde_json::to_string_pretty(&report.to_json()).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    let (day, input) = load_day(matches)?;
+    let parsed = (day.parse)(&input).map_err(|e| format!("Parsing the input failed: {}", e.render(&input)))?;
+    for (puzzle, solver) in day.puzzles(part) {
+        let token = timeout.map(CancellationToken::with_timeout);
+        print_answer(puzzle, run_with(token, || solver(&parsed)), &input);
+    }
+    Ok(())
 }
 
-fn main() {
-    let a: Vec<String> = args().collect();
+fn run_all(matches: &Matches) -> Result<(), String>
+{
+    let years = match parse_number_arg(matches, "year")? {
+        Some(year) => vec![year],
+        None => available_years()
+    };
+    let format = parse_format(matches)?;
+    let timeout = parse_timeout(matches)?;
+    let jobs = match matches.option("jobs").map(parse_usize) {
+        None => default_jobs(),
+        Some(Ok(0)) => return Err("Need at least one job".to_string()),
+        Some(Ok(v)) => v,
+        Some(Err(e)) => return Err(e.to_string())
+    };
+
+    if format == Format::Text {
+        println!(" Year | Day | Puzzle | {:<40} | {:>12}", "Answer", "Time");
+        println!("------+-----+--------+-{:-<40}-+-{:->12}", "", "");
+    }
+
+    // Days run on a pool of threads, but their results are shown in order. A day that is not implemented has no report.
+    let days: Vec<(i32, i32)> = years.iter().flat_map(|&year| (1..=25).map(move |day| (year, day))).collect();
+    let run = |(year, day_number): (i32, i32)| {
+        get_day(year, day_number).ok().map(|day| match read_input(year, day_number) {
+            Ok(input) => DayReport::run(year, day_number, &day, &input, None, timeout),
+            Err(err) => DayReport::failed(year, day_number, err)
+        })
+    };
+
+    let start = Instant::now();
+    let mut total = Duration::ZERO;
+    let mut reports = vec![];
+    // The results come in the order of the days.
+    let mut order = days.clone().into_iter();
+    for_each_ordered(days, jobs, run, |result| {
+        let (year, day_number) = order.next().unwrap_or_default();
+        let report = match result {
+            Ok(Some(report)) => report,
+            // DayReport::run already reports panics of the puzzles, so this only happens when e.g. reading the input panics.
+            Err(message) => DayReport::failed(year, day_number, Error::Panicked(message)),
+            Ok(None) => {
+                if format == Format::Text {
+                    println!(" {} | {:02}  | {:>6} | {:<40} |", year, day_number, "-", "skipped: not implemented");
+                }
+                return;
+            }
+        };
+        total += report.duration();
+
+        match format {
+            Format::Text => print_report_rows(&report),
+            Format::Json => reports.push(report.to_json())
+        }
+    });
+
+    match format {
+        Format::Text => {
+            println!("------+-----+--------+-{:-<40}-+-{:->12}", "", "");
+            println!("      |     | {:>6} | {:<40} | {:>12.3?}", "", "Total", total);
+            println!("      |     | {:>6} | {:<40} | {:>12.3?}", "", format!("Elapsed (jobs: {})", jobs), start.elapsed());
+        }
+        Format::Json => {
+            println!("{}", serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?);
+        }
+    }
+    Ok(())
+}
 
-    if a.len() < 3 {
-        print_usage();
-        return;
+// Prints the rows of the `all` table for a single day.
+fn print_report_rows(report: &DayReport)
+{
+    let (year, day_number) = (report.year, report.day);
+    match (&report.error, report.parse_duration) {
+        (Some(err @ Error::MissingInput(_)), None) => {
+            println!(" {} | {:02}  | {:>6} | {:<40} |", year, day_number, "-", format!("skipped: no input ({})", err));
+            return;
+        }
+        (Some(err), None) => {
+            println!(" {} | {:02}  | {:>6} | {:<40} |", year, day_number, "-", format!("error: {}", err));
+            return;
+        }
+        (Some(err), Some(duration)) => {
+            println!(" {} | {:02}  | {:>6} | {:<40} | {:>12.3?}", year, day_number, "parse", format!("error: {}", err), duration);
+            return;
+        }
+        (None, duration) => {
+            println!(" {} | {:02}  | {:>6} | {:<40} | {:>12.3?}", year, day_number, "parse", "", duration.unwrap_or_default());
+        }
     }
 
-    match a[1].as_str() {
-        "day" => {
-            run_day(&a[2])
+    for part in &report.parts {
+        let text = match &part.result {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {}", err)
+        };
+        let mut lines = text.lines();
+        println!(" {} | {:02}  | {:>6} | {:<40} | {:>12.3?}", year, day_number, part.part, lines.next().unwrap_or(""), part.duration);
+        for line in lines {
+            println!("      |     | {:>6} | {:<40} |", "", line);
         }
-        "add" => {
-            add_day(&a[2])
+    }
+}
+
+fn bench_day(matches: &Matches) -> Result<(), String>
+{
+    let part = parse_part(matches)?;
+    let iterations = match matches.option("iterations").map(parse_usize) {
+        None => 10,
+        Some(Ok(0)) => return Err("Need at least one iteration".to_string()),
+        Some(result) => result.map_err(|e| e.to_string())?
+    };
+    let (day, input) = load_day(matches)?;
+
+    println!("Puzzle | {:>12} | {:>12} | {:>12} | {:>12}", "Min", "Median", "Mean", "Std. dev.");
+    println!("-------+-{:->12}-+-{:->12}-+-{:->12}-+-{:->12}", "", "", "", "");
+
+    // Parse once up front, so a parse failure is reported instead of timed, and the puzzles have an input to run on.
+    let parsed = match (day.parse)(&input) {
+        Ok(v) => v,
+        Err(err) => {
+            println!("{:>6} | error: {}", "parse", err);
+            return Ok(());
         }
-        _ => {
-            print_usage();
+    };
+    print_statistics("parse", &sample(iterations, || (day.parse)(&input)));
+
+    for (puzzle, solver) in day.puzzles(part) {
+        // Run once up front, so a failing puzzle is reported instead of timed.
+        if let Err(err) = solver(&parsed) {
+            println!("{:>6} | error: {}", puzzle, err);
+            continue;
         }
+
+        print_statistics(&puzzle.to_string(), &sample(iterations, || solver(&parsed)));
+    }
+    Ok(())
+}
+
+fn print_statistics(name: &str, samples: &[Duration])
+{
+    if let Some(stats) = Statistics::from_samples(samples) {
+        println!("{:>6} | {:>12.3?} | {:>12.3?} | {:>12.3?} | {:>12.3?}", name, stats.min, stats.median, stats.mean, stats.std_dev);
     }
 }
 
-fn run_day(input: &str)
+fn print_answer(puzzle: i32, result: Result<Answer, Error>, input: &str)
 {
-    let result: Result<(String, Day), String> = parse_i32(input)
-        .and_then(|d| read_input(d).and_then(|input| get_day(d).and_then(|day| Ok((input, day)))));
     match result {
-        Ok((input, day)) => {
-            (day.puzzle1)(&input);
-            (day.puzzle2)(&input);
+        Ok(answer) if answer.is_multiline() => {
+            println!("Puzzle {} answer:\n{}", puzzle, answer);
+        }
+        Ok(answer) => {
+            println!("Puzzle {} answer: {}", puzzle, answer);
         }
         Err(err) => {
-            eprintln!("{}", err);
+            eprintln!("Puzzle {} failed: {}", puzzle, err.render(input));
         }
     }
 }
 
-fn add_day(input: &str)
+fn verify(matches: &Matches) -> Result<(), String>
 {
-    // This is going to be fun. Write code to modify the running code! Woohoo!
-    match parse_i32(input) {
-        Ok(day) => {
-            match util::create_day::create_day(day) {
-                Ok(_) => { println!("Successfully added day {}", day); }
-                Err(e) => { panic!("{}", e); }
+    let years = match parse_number_arg(matches, "year")? {
+        Some(year) => vec![year],
+        None => available_years()
+    };
+    let day = parse_number_arg(matches, "day")?;
+
+    let mut failures = 0;
+    for year in years {
+        let days: Vec<i32> = match day {
+            Some(day) => vec![day],
+            None => available_days(year)
+        };
+        let answers = read_answers(year).map_err(|e| e.to_string())?;
+
+        for day_number in days {
+            let (input, day) = match read_input(year, day_number).and_then(|input| get_day(year, day_number).map(|day| (input, day))) {
+                Ok(v) => v,
+                Err(err) => {
+                    failures += 1;
+                    println!("{} day {:02}: ERROR ({})", year, day_number, err);
+                    continue;
+                }
+            };
+
+            let parsed = (day.parse)(&input);
+            for (puzzle, solver) in day.puzzles(None) {
+                let result = parsed.as_ref().map_err(|e| e.clone()).and_then(solver);
+                match answers.verify(day_number, puzzle, result) {
+                    Verification::Pass => {
+                        println!("{} day {:02} puzzle {}: PASS", year, day_number, puzzle);
+                    }
+                    Verification::Fail { expected, actual } => {
+                        failures += 1;
+                        println!("{} day {:02} puzzle {}: FAIL (expected {}, got {})", year, day_number, puzzle, expected, actual);
+                    }
+                    Verification::Missing { actual } => {
+                        println!("{} day {:02} puzzle {}: MISSING (got {})", year, day_number, puzzle, actual);
+                    }
+                    Verification::Error(err) => {
+                        failures += 1;
+                        println!("{} day {:02} puzzle {}: ERROR ({})", year, day_number, puzzle, err);
+                    }
+                }
             }
         }
-        Err(err) => {
-            panic!("{}", err);
+    }
+
+    if failures > 0 {
+        Err(format!("{} puzzle(s) did not pass verification", failures))
+    } else {
+        Ok(())
+    }
+}
+
+fn add_day(matches: &Matches) -> Result<(), String>
+{
+    // This is going to be fun. Write code to modify the running code! Woohoo!
+    let (year, day) = parse_year_and_day(matches)?;
+    let template = matches.option("template").unwrap_or("default");
+    let title = matches.option("title");
+    if matches.flag("dry-run") {
+        println!("{}", render_plan(&plan_day(year, day, template, title)?));
+        return Ok(());
+    }
+
+    for path in create_day(year, day, template, title)? {
+        println!("Created {}", path.display());
+    }
+    println!("Successfully added day {} of {}", day, year);
+    Ok(())
+}
+
+fn import(matches: &Matches) -> Result<(), String>
+{
+    let (year, day) = parse_year_and_day(matches)?;
+    let input = InputSource::from_path(matches.arg("file").unwrap_or_default()).read().map_err(|e| e.to_string())?;
+    match import_input(year, day, &input, matches.flag("force"))? {
+        Import::Imported(path) => println!("Imported the input for day {} of {} into {}", day, year, path.display()),
+        Import::Unchanged(path) => println!("The input for day {} of {} in {} is already up to date", day, year, path.display()),
+    }
+    Ok(())
+}
+
+fn render_day(matches: &Matches) -> Result<(), String>
+{
+    let part = parse_part(matches)?.unwrap_or(1);
+    let (year, day_number) = parse_year_and_day(matches)?;
+    let (day, input) = load_day(matches)?;
+
+    let parsed = (day.parse)(&input).map_err(|e| format!("Parsing the input failed: {}", e.render(&input)))?;
+    let picture = (day.render)(&parsed, part).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Day {} of {} has no render mode", day_number, year))?;
+
+    match matches.option("output") {
+        Some(path) => {
+            write(path, to_ppm(&picture)).map_err(|e| format!("Could not write image: {:?}\nError: {}", path, e))?;
+            println!("Written {} ({}x{})", path, picture.bounds.width, picture.bounds.height);
         }
+        None => println!("{}", to_text(&picture, std::io::stdout().is_terminal()))
     }
-}
\ No newline at end of file
+    Ok(())
+}
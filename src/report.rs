@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+use advent_of_code_2021::{Answer, Day, Error};
+use advent_of_code_2021::error::catch_panic;
+use advent_of_code_2021::util::cancel::{run_with, CancellationToken};
+use serde_json::{json, Value};
+
+// The outcome of running the puzzles of a single day, for the `all` table and the JSON output.
+pub struct DayReport {
+    pub year: i32,
+    pub day: i32,
+    // Why the puzzles did not run: either the input could not be read (no parse_duration), or it could not be parsed.
+    // Panics in parsing count as the latter; other panics while running the day as the former.
+    pub error: Option<Error>,
+    pub parse_duration: Option<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+pub struct PartReport {
+    pub part: i32,
+    pub result: Result<Answer, Error>,
+    pub duration: Duration,
+}
+
+fn time<R, F>(f: F) -> (R, Duration) where F: FnOnce() -> R
+{
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+impl DayReport {
+    // Parses the input once, and runs the given puzzle (or both) on it. With a timeout, each puzzle is cancelled once it
+    // runs longer than that (as far as the solver checks for it). A panic in parsing or in a puzzle is reported as its
+    // error.
+    pub fn run(year: i32, day_number: i32, day: &Day, input: &str, part: Option<i32>, timeout: Option<Duration>) -> Self {
+        let (parsed, parse_duration) = time(|| catch_panic(|| (day.parse)(input)));
+        let parsed = match parsed {
+            Ok(v) => v,
+            Err(err) => return DayReport { year, day: day_number, error: Some(err), parse_duration: Some(parse_duration), parts: vec![] }
+        };
+
+        let parts = day.puzzles(part).into_iter()
+            .map(|(puzzle, solver)| {
+                let token = timeout.map(CancellationToken::with_timeout);
+                let (result, duration) = time(|| catch_panic(|| run_with(token, || solver(&parsed))));
+                PartReport { part: puzzle, result, duration }
+            })
+            .collect();
+        DayReport { year, day: day_number, error: None, parse_duration: Some(parse_duration), parts }
+    }
+
+    // A day that did not run, as its input could not be read, or the day panicked before it was parsed.
+    pub fn failed(year: i32, day: i32, error: Error) -> Self {
+        DayReport { year, day, error: Some(error), parse_duration: None, parts: vec![] }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.parse_duration.unwrap_or_default() + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+    }
+
+    // E.g. { "year": 2021, "day": 1, "error": null, "parse_duration_ms": 0.5,
+    //        "parts": [{ "part": 1, "answer": "1162", "error": null, "duration_ms": 0.03 }, ...] }
+    pub fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "error": self.error.as_ref().map(|e| e.to_string()),
+            "parse_duration_ms": self.parse_duration.map(millis),
+            "parts": self.parts.iter().map(|p| json!({
+                "part": p.part,
+                "answer": p.result.as_ref().ok().map(|a| a.to_string()),
+                "error": p.result.as_ref().err().map(|e| e.to_string()),
+                "duration_ms": millis(p.duration),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use advent_of_code_2021::{get_day, Day, Error};
+    use serde_json::json;
+    use crate::report::DayReport;
+
+    #[test]
+    fn test_to_json() {
+        let day = get_day(2021, 1).unwrap();
+        let mut value = DayReport::run(2021, 1, &day, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263", None, None).to_json();
+        assert!(value["parse_duration_ms"].is_f64());
+        assert!(value["parts"][1]["duration_ms"].is_f64());
+
+        value["parse_duration_ms"] = json!(null);
+        value["parts"][0]["duration_ms"] = json!(null);
+        value["parts"][1]["duration_ms"] = json!(null);
+        assert_eq!(value, json!({
+            "year": 2021, "day": 1, "error": null, "parse_duration_ms": null,
+            "parts": [
+                { "part": 1, "answer": "7", "error": null, "duration_ms": null },
+                { "part": 2, "answer": "5", "error": null, "duration_ms": null },
+            ]
+        }));
+
+        let value = DayReport::run(2021, 1, &day, "199\nabc", Some(2), None).to_json();
+        assert_eq!(value["error"], json!("Parse error at line 2: Invalid u128 'abc': invalid digit found in string"));
+        assert_eq!(value["parts"], json!([]));
+
+        let value = DayReport::failed(2021, 1, Error::MissingInput("No input".to_string())).to_json();
+        assert_eq!(value["error"], json!("Missing input: No input"));
+        assert_eq!(value["parse_duration_ms"], json!(null));
+    }
+
+    #[test]
+    fn test_panic() {
+        let day = get_day(2021, 1).unwrap();
+        let input = "199\n200\n208";
+        let panicking = Day { puzzle2: |_| panic!("oops"), ..day };
+        let report = DayReport::run(2021, 1, &panicking, input, None, None);
+        assert_eq!(report.error, None);
+        assert_eq!(report.parts[0].result.as_ref().map(|a| a.to_string()), Ok("2".to_string()));
+        assert_eq!(report.parts[1].result, Err(Error::Panicked("oops".to_string())));
+
+        let panicking = Day { parse: |_| panic!("no parse"), ..day };
+        let report = DayReport::run(2021, 1, &panicking, input, None, None);
+        assert_eq!(report.error, Some(Error::Panicked("no parse".to_string())));
+        assert!(report.parts.is_empty());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day = get_day(2021, 23).unwrap();
+        let input = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#A#\n  #########";
+        let report = DayReport::run(2021, 23, &day, input, Some(1), Some(Duration::ZERO));
+        assert_eq!(report.parts[0].result, Err(Error::TimedOut(Duration::ZERO)));
+        assert_eq!(report.to_json()["parts"][0]["error"], json!("Timed out after 0ns"));
+    }
+}
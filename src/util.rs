@@ -3,3 +3,10 @@ pub mod number;
 pub mod geometry;
 pub mod create_day;
 pub mod collection;
+pub mod answers;
+pub mod bench;
+pub mod import;
+pub mod parallel;
+pub mod cancel;
+pub mod search;
+pub mod render;
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use crate::days::Answer;
+use crate::error::Error;
+use crate::util::input::year_dir;
+
+const ANSWERS_FILE: &str = "answers.json";
+
+// Known-correct answers of a single year, keyed by day and puzzle number. Every year has its own file, which looks like:
+// { "1": { "1": "1292", "2": "1262" }, "2": { ... } }
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct Answers {
+    entries: HashMap<(i32, i32), String>,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum Verification {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(Error),
+}
+
+impl Answers {
+    pub fn get(&self, day: i32, puzzle: i32) -> Option<&String> {
+        self.entries.get(&(day, puzzle))
+    }
+
+    pub fn verify(&self, day: i32, puzzle: i32, result: Result<Answer, Error>) -> Verification {
+        let actual = match result {
+            Ok(answer) => answer.to_string(),
+            Err(e) => return Verification::Error(e)
+        };
+
+        match self.get(day, puzzle) {
+            Some(expected) if expected == &actual => Verification::Pass,
+            Some(expected) => Verification::Fail { expected: expected.clone(), actual },
+            None => Verification::Missing { actual }
+        }
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let raw: HashMap<String, HashMap<String, String>> = serde_json::from_str(value)
+            .map_err(|e| Error::parse(format!("Could not parse answers: {}", e)).at(e.line(), e.column()))?;
+
+        let mut entries = HashMap::new();
+        for (day, puzzles) in raw {
+            let day_number = day.parse::<i32>().map_err(|_| Error::parse(format!("Invalid day '{}' in answers", day)))?;
+            for (puzzle, answer) in puzzles {
+                let puzzle_number = match puzzle.parse::<i32>() {
+                    Ok(p @ 1..=2) => p,
+                    _ => return Err(Error::parse(format!("Invalid puzzle '{}' for day {} in answers", puzzle, day_number)))
+                };
+                entries.insert((day_number, puzzle_number), answer);
+            }
+        }
+
+        Ok(Answers { entries })
+    }
+}
+
+pub fn read_answers(year: i32) -> Result<Answers, Error> {
+    let path = year_dir(year).join(ANSWERS_FILE);
+    let content = read_to_string(&path).map_err(|e| Error::MissingInput(format!("Could not read {}: {}", path.display(), e)))?;
+    Answers::try_from(content.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::Answer;
+    use crate::error::Error;
+    use crate::util::answers::{Answers, Verification};
+
+    const EXAMPLE_ANSWERS: &str = r#"{
+        "1": { "1": "7", "2": "5" },
+        "13": { "1": "17", "2": ".##.\n#..#" }
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::try_from(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(answers.get(1, 1), Some(&"7".to_string()));
+        assert_eq!(answers.get(13, 2), Some(&".##.\n#..#".to_string()));
+        assert_eq!(answers.get(2, 1), None);
+
+        assert!(Answers::try_from(r#"{ "1": { "3": "7" } }"#).is_err());
+        assert!(Answers::try_from(r#"{ "one": { "1": "7" } }"#).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::try_from(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(answers.verify(1, 1, Ok(Answer::Integer(7))), Verification::Pass);
+        assert_eq!(answers.verify(1, 2, Ok(Answer::Integer(6))), Verification::Fail { expected: "5".to_string(), actual: "6".to_string() });
+        assert_eq!(answers.verify(2, 1, Ok(Answer::Integer(6))), Verification::Missing { actual: "6".to_string() });
+        assert_eq!(answers.verify(13, 2, Ok(Answer::Text(".##.\n#..#".to_string()))), Verification::Pass);
+        assert_eq!(answers.verify(1, 1, Err(Error::no_solution("nope"))), Verification::Error(Error::no_solution("nope")));
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Statistics {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let iterations = sorted.len();
+        let median = if iterations.is_multiple_of(2) {
+            (sorted[iterations / 2 - 1] + sorted[iterations / 2]) / 2
+        } else {
+            sorted[iterations / 2]
+        };
+
+        let mean_nanos = sorted.iter().map(|d| d.as_nanos() as f64).sum::<f64>() / iterations as f64;
+        let variance = sorted.iter().map(|d| (d.as_nanos() as f64 - mean_nanos).powi(2)).sum::<f64>() / iterations as f64;
+
+        Some(Statistics {
+            iterations,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean_nanos.round() as u64),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+// Runs the given function the given number of times, returning the duration of every run.
+pub fn sample<F, R>(iterations: usize, mut f: F) -> Vec<Duration> where F: FnMut() -> R {
+    (0..iterations).map(|_| {
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        // Make sure the result is not optimized away, and not dropped within the measured time.
+        std::hint::black_box(result);
+        elapsed
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::util::bench::{sample, Statistics};
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn test_statistics() {
+        assert_eq!(Statistics::from_samples(&[]), None);
+        assert_eq!(Statistics::from_samples(&[ms(4), ms(2), ms(4), ms(4), ms(5), ms(5), ms(7), ms(9)]), Some(Statistics {
+            iterations: 8,
+            min: ms(2),
+            median: ms(4) + Duration::from_micros(500),
+            mean: ms(5),
+            std_dev: ms(2),
+        }));
+        assert_eq!(Statistics::from_samples(&[ms(3), ms(1), ms(2)]).map(|s| (s.min, s.median)), Some((ms(1), ms(2))));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut calls = 0;
+        let samples = sample(5, || calls += 1);
+        assert_eq!(samples.len(), 5);
+        assert_eq!(calls, 5);
+    }
+}
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+use crate::error::Error;
+
+// Cooperative cancellation of long-running puzzles. The runner gives the puzzle running on the current thread a token
+// with a timeout, and solvers call `check()` in their main loops to stop once it has expired. Solvers that never check
+// simply run to completion.
+#[derive(Copy, Clone, Debug)]
+pub struct CancellationToken {
+    started: Instant,
+    timeout: Duration,
+}
+
+impl CancellationToken {
+    pub fn with_timeout(timeout: Duration) -> Self {
+        CancellationToken { started: Instant::now(), timeout }
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        // Also expired right away for a zero timeout, however quickly it is checked.
+        if self.started.elapsed() >= self.timeout {
+            Err(Error::TimedOut(self.timeout))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<CancellationToken>> = const { Cell::new(None) };
+}
+
+// Runs `f` with the given token as the token of the current thread, restoring the previous one afterwards (also when
+// `f` panics).
+pub fn run_with<R, F>(token: Option<CancellationToken>, f: F) -> R where F: FnOnce() -> R
+{
+    struct Restore(Option<CancellationToken>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(token)));
+    f()
+}
+
+// Fails with Error::TimedOut once the token of the current thread has expired. Without a token, this always succeeds.
+pub fn check() -> Result<(), Error> {
+    match CURRENT.with(|current| current.get()) {
+        Some(token) => token.check(),
+        None => Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::error::Error;
+    use crate::util::cancel::{check, run_with, CancellationToken};
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(), Ok(()));
+        assert_eq!(run_with(Some(CancellationToken::with_timeout(Duration::from_secs(60))), check), Ok(()));
+        assert_eq!(run_with(Some(CancellationToken::with_timeout(Duration::ZERO)), || {
+            std::thread::sleep(Duration::from_millis(1));
+            check()
+        }), Err(Error::TimedOut(Duration::ZERO)));
+        assert_eq!(run_with(Some(CancellationToken::with_timeout(Duration::ZERO)), check), Err(Error::TimedOut(Duration::ZERO)));
+        assert_eq!(check(), Ok(()));
+    }
+
+    #[test]
+    fn test_run_with_nested() {
+        let expired = Some(CancellationToken::with_timeout(Duration::ZERO));
+        run_with(expired, || {
+            std::thread::sleep(Duration::from_millis(1));
+            assert_eq!(run_with(None, check), Ok(()));
+            assert!(check().is_err());
+        });
+    }
+}
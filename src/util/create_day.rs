@@ -1,74 +1,247 @@
 use crate::days::get_day;
-use std::fs::{read_to_string, write,};
-use std::path::{Path};
-use regex::{Captures, RegexBuilder};
+use crate::error::Error;
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir, remove_file, rename, write};
+use std::path::{Path, PathBuf};
+use crate::util::input::{examples_dir, resources_dir, year_dir};
 use handlebars::{Handlebars};
 use serde_json::{json};
 
-pub fn create_day(day: i32) -> Result<(), String> {
-    match get_day(day) {
-        Err(_) => {
-            let source_file_name = format!("src/days/day{:02}.rs", day);
-            let source_path = Path::new(&source_file_name);
-            let template_file_name = format!("resources/day{:02}.txt", day);
-            let input_path = Path::new(&template_file_name);
-            let module_file_name = "src/days.rs".to_string();
-            let module_path = Path::new(&module_file_name);
-
-            if source_path.exists() {
-                return Err(format!("Source file for day {} already exists.", day));
-            }
-            if input_path.exists() {
-                return Err(format!("Input file for day {} already exists.", day));
-            }
+// What `add` writes as the input of a new day, until the real input is imported.
+pub const PLACEHOLDER_INPUT: &str = "TODO: Add Content Here";
 
-            let template = match read_to_string("resources/day.rs.template") {
-                Ok(v) => { v }
-                Err(e) => { return Err(format!("Could not read day template: {}", e)); }
-            };
-            let days_mod_content = match read_to_string(Path::new(&module_path)) {
-                Ok(v) => { v }
-                Err(e) => { return Err(format!("Could not read days module file: {}", e)); }
-            };
-
-            let import_regex = match RegexBuilder::new("^(\\s*)(// « add day import »)").multi_line(true).build() {
-                Ok(r) => { r }
-                Err(e) => { return Err(format!("{}", e)); }
-            };
-            let match_regex = match RegexBuilder::new("^(\\s*)(// « add day match »)").multi_line(true).build() {
-                Ok(r) => { r }
-                Err(e) => { return Err(format!("{}", e)); }
-            };
-
-            if !import_regex.is_match(days_mod_content.as_str()) {
-                println!("{}", days_mod_content);
-                return Err("Could not find import comment in days module".to_string());
-            }
-            if !match_regex.is_match(days_mod_content.as_str()) {
-                return Err("Could not find match comment in days module".to_string());
-            }
+#[derive(Eq, PartialEq, Debug)]
+pub struct PlannedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+// Creates src/days/<year>/dayNN.rs from the given template, a placeholder input file and an empty example input. The
+// new day is picked up by build.rs on the next build, so no other source needs to change. Either all files are written,
+// or none of them are. Returns the paths of the created files.
+pub fn create_day(year: i32, day: i32, template: &str, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    let files = plan_day(year, day, template, title)?;
+    write_all(&files)?;
+    Ok(files.into_iter().map(|f| f.path).collect())
+}
+
+// Determines the files create_day would write for the given day, without touching the file system.
+pub fn plan_day(year: i32, day: i32, template: &str, title: Option<&str>) -> Result<Vec<PlannedFile>, String> {
+    match get_day(year, day) {
+        Ok(_) => return Err(format!("Day {} of {} already exists!", day, year)),
+        Err(err @ Error::InvalidDay(_)) => return Err(err.to_string()),
+        Err(_) => {}
+    }
+
+    let module = format!("day{:02}", day);
+    let source_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("days").join(year.to_string()).join(format!("{}.rs", module));
+    let input_path = year_dir(year).join(format!("{}.txt", module));
+    let example_path = examples_dir(year).join(format!("{}.txt", module));
+
+    if source_path.exists() {
+        return Err(format!("Source file for day {} of {} already exists.", day, year));
+    }
+    if input_path.exists() {
+        return Err(format!("Input file for day {} of {} already exists.", day, year));
+    }
+    if example_path.exists() {
+        return Err(format!("Example file for day {} of {} already exists.", day, year));
+    }
+
+    let day_content = render_template(template, &json!({
+        "year": year,
+        "day": day,
+        "day_padded": format!("{:02}", day),
+        "title": title,
+        "solver": format!("Day{:02}", day),
+    }))?;
 
-            let res1 = import_regex.replace(days_mod_content.as_str(), |caps: &Captures| {
-                format!("{ws}mod day{day:02};\n{ws}use day{day:02}::DAY{day};\n{ws}{comm}", ws = &caps[1], comm = &caps[2], day = day)
-            });
-            let module_content = match_regex.replace(res1.as_ref(), |caps: &Captures| {
-                format!("{ws}{day} => Ok(DAY{day}),\n{ws}{comm}", ws = &caps[1], comm = &caps[2], day = day)
-            });
+    Ok(vec![
+        PlannedFile { path: source_path, content: day_content },
+        PlannedFile { path: input_path, content: PLACEHOLDER_INPUT.to_string() },
+        PlannedFile { path: example_path, content: String::new() },
+    ])
+}
 
-            let handlebars = Handlebars::new();
-            let day_content = match handlebars.render_template(template.as_str(), &json!({ "day": day })) {
-                Ok(v) => { v }
-                Err(e) => { return Err(format!("{}", e)); }
-            };
+// The templates in resources/templates, by name: <name>.rs.template.
+pub fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = read_dir(templates_dir()).map(|entries| entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| name.strip_suffix(".rs.template").map(|n| n.to_string()))
+        .collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
 
-            match write(input_path, "TODO: Add Content Here") { Err(e) => { return Err(format!("Could not write input file: {:?}\nError: {}", input_path, e)); }, _ => {} }
-            match write(source_path, day_content) { Err(e) => { return Err(format!("Could not write day file: {:?}\nError: {}", source_path, e)); }, _ => {} }
-            match write(module_path, module_content.to_string()) { Err(e) => { return Err(format!("Could not write module file: {:?}\nError: {}", module_path, e)); }, _ => {} }
+fn templates_dir() -> PathBuf {
+    resources_dir().join("templates")
+}
 
-            Ok(())
+fn render_template(name: &str, context: &serde_json::Value) -> Result<String, String> {
+    if !available_templates().iter().any(|n| n == name) {
+        return Err(format!("Unknown template '{}', available: {}", name, available_templates().join(", ")));
+    }
+
+    let template = read_to_string(templates_dir().join(format!("{}.rs.template", name)))
+        .map_err(|e| format!("Could not read day template: {}", e))?;
+    let mut handlebars = Handlebars::new();
+    // The output is Rust source, not HTML; e.g. a title like "Treetop Tree House & Co" should end up as is.
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.render_template(template.as_str(), context).map_err(|e| format!("{}", e))
+}
+
+// Renders the planned files as a diff against the current (empty) state, for a dry run.
+pub fn render_plan(files: &[PlannedFile]) -> String {
+    let mut lines = vec![];
+    for file in files {
+        lines.push("--- /dev/null".to_string());
+        lines.push(format!("+++ {}", file.path.display()));
+        lines.push(format!("@@ -0,0 +1,{} @@", file.content.lines().count()));
+        lines.extend(file.content.lines().map(|line| format!("+{}", line)));
+    }
+    lines.join("\n")
+}
+
+// Writes all given files, or none of them. Every file is first written to a temporary file next to its destination, and
+// only when that succeeded for all of them are they moved into place. On failure, everything written so far (including
+// newly created folders) is removed again.
+fn write_all(files: &[PlannedFile]) -> Result<(), String> {
+    let mut transaction = Transaction::default();
+    let result = transaction.write_all(files);
+    if result.is_err() {
+        transaction.rollback();
+    }
+    result
+}
+
+#[derive(Default)]
+struct Transaction {
+    created_dirs: Vec<PathBuf>,
+    staged: Vec<PathBuf>,
+    committed: Vec<PathBuf>,
+}
+
+impl Transaction {
+    fn write_all(&mut self, files: &[PlannedFile]) -> Result<(), String> {
+        if let Some(file) = files.iter().find(|f| f.path.exists()) {
+            return Err(format!("File already exists: {:?}", file.path));
+        }
+
+        for file in files {
+            if let Some(dir) = file.path.parent() {
+                self.create_dir(dir)?;
+            }
+            let staged_path = staged_path(&file.path);
+            write(&staged_path, &file.content).map_err(|e| format!("Could not write file: {:?}\nError: {}", staged_path, e))?;
+            self.staged.push(staged_path);
         }
-        Ok(_) => {
-            Err(format!("Day {} already exists!", day))
+
+        for (file, staged_path) in files.iter().zip(self.staged.clone()) {
+            rename(&staged_path, &file.path).map_err(|e| format!("Could not write file: {:?}\nError: {}", file.path, e))?;
+            self.committed.push(file.path.clone());
         }
+        Ok(())
+    }
+
+    fn create_dir(&mut self, dir: &Path) -> Result<(), String> {
+        let missing: Vec<PathBuf> = dir.ancestors().take_while(|d| !d.exists()).map(Path::to_path_buf).collect();
+        create_dir_all(dir).map_err(|e| format!("Could not create folder: {:?}\nError: {}", dir, e))?;
+        // Outermost folder first, so rolling back in reverse removes the innermost folders first.
+        self.created_dirs.extend(missing.into_iter().rev());
+        Ok(())
+    }
+
+    // Best effort: errors are ignored, as there is nothing sensible left to do about them.
+    fn rollback(&mut self) {
+        for path in self.committed.iter().chain(self.staged.iter()) {
+            let _ = remove_file(path);
+        }
+        for dir in self.created_dirs.iter().rev() {
+            let _ = remove_dir(dir);
+        }
+    }
+}
+
+// The temporary file a file is written to before it is moved into place.
+pub(crate) fn staged_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, read_to_string, remove_dir_all};
+    use std::path::PathBuf;
+    use crate::util::create_day::{available_templates, plan_day, render_plan, write_all, PlannedFile};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("aoc-create-day-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_plan_day() {
+        let files = plan_day(2015, 7, "default", None).unwrap();
+        let names: Vec<String> = files.iter().map(|f| f.path.strip_prefix(env!("CARGO_MANIFEST_DIR")).unwrap().display().to_string()).collect();
+        assert_eq!(names, vec!["src/days/2015/day07.rs", "resources/2015/day07.txt", "resources/2015/examples/day07.txt"]);
+        assert!(files[0].content.contains("pub struct Day07;"));
+        assert!(files[0].content.contains("InputSource::Example(2015, 7, None)"));
+        assert!(files.iter().all(|f| !f.path.exists()));
+
+        assert_eq!(plan_day(2021, 1, "default", None), Err("Day 1 of 2021 already exists!".to_string()));
+        assert_eq!(plan_day(2021, 26, "default", None), Err("Invalid day 26, expected a day from 1 to 25".to_string()));
+    }
+
+    #[test]
+    fn test_plan_day_templates() {
+        let source = |template, title| plan_day(2015, 7, template, title).map(|files| files[0].content.clone());
+
+        assert!(source("default", None).unwrap().starts_with("// Day 7 of 2015\n"));
+        assert!(source("default", Some("Sea & Cucumber")).unwrap().starts_with("// Day 7 of 2015: Sea & Cucumber\n"));
+        assert!(source("default", None).unwrap().contains("use crate::days::y2015::day07::Day07;"));
+        assert!(source("grid", None).unwrap().contains("type Input = Grid<usize>;"));
+        assert!(source("parse-lines", None).unwrap().contains("type Input = Vec<Line>;"));
+        assert_eq!(source("unknown", None), Err("Unknown template 'unknown', available: default, grid, parse-lines".to_string()));
+    }
+
+    #[test]
+    fn test_available_templates() {
+        assert_eq!(available_templates(), vec!["default", "grid", "parse-lines"]);
+    }
+
+    #[test]
+    fn test_render_plan() {
+        let files = vec![PlannedFile { path: PathBuf::from("a/b.txt"), content: "one\ntwo\n".to_string() }];
+        assert_eq!(render_plan(&files), "--- /dev/null\n+++ a/b.txt\n@@ -0,0 +1,2 @@\n+one\n+two");
+    }
+
+    #[test]
+    fn test_write_all() {
+        let dir = scratch_dir("write");
+        let files = vec![
+            PlannedFile { path: dir.join("src").join("a.rs"), content: "a".to_string() },
+            PlannedFile { path: dir.join("resources").join("b.txt"), content: "b".to_string() },
+        ];
+        assert_eq!(write_all(&files), Ok(()));
+        assert_eq!(read_to_string(dir.join("src").join("a.rs")).unwrap(), "a");
+        assert_eq!(read_to_string(dir.join("resources").join("b.txt")).unwrap(), "b");
+        assert!(!dir.join("src").join(".a.rs.tmp").exists());
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_all_rolls_back() {
+        let dir = scratch_dir("rollback");
+        create_dir_all(&dir).unwrap();
+        // The first file cannot be moved into place, as the second one needs a folder at the same path.
+        let files = vec![
+            PlannedFile { path: dir.join("new").join("a.rs"), content: "a".to_string() },
+            PlannedFile { path: dir.join("new").join("a.rs").join("b.txt"), content: "b".to_string() },
+        ];
+        assert!(write_all(&files).is_err());
+        assert!(!dir.join("new").exists());
+        remove_dir_all(&dir).unwrap();
     }
-}
\ No newline at end of file
+}
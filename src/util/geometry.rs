@@ -4,6 +4,7 @@ use std::fmt;
 use std::hash::Hash;
 use std::ops::{Range};
 use std::str::FromStr;
+use crate::error::Error;
 use crate::util::number;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
@@ -25,27 +26,27 @@ impl From<(isize, isize)> for Point {
 }
 
 impl TryFrom<(usize, usize)> for Point {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: (usize, usize)) -> Result<Self, Self::Error> {
-        let x: isize = isize::try_from(value.0).map_err(|e| format!("{}", e))?;
-        let y: isize = isize::try_from(value.1).map_err(|e| format!("{}", e))?;
+        let x: isize = isize::try_from(value.0).map_err(|e| Error::invalid_state(format!("{}", e)))?;
+        let y: isize = isize::try_from(value.1).map_err(|e| Error::invalid_state(format!("{}", e)))?;
         Ok(Point { x, y })
     }
 }
 
 impl FromStr for Point {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts_result: Result<Vec<isize>, String> = s.split(",").map(|p| number::parse_isize(p)).collect();
+        let parts_result: Result<Vec<isize>, Error> = s.split(",").map(|p| number::parse_isize(p)).collect();
         let parts = match parts_result {
             Ok(v) => v,
             Err(e) => return Err(e)
         };
         match parts.len() {
             2 => Ok((parts[0], parts[1]).into()),
-            _ => Err(format!("Invalid str format for Point '{}', expected 'x,y'", s))
+            _ => Err(Error::parse(format!("Invalid str format for Point '{}', expected 'x,y'", s)))
         }
     }
 }
@@ -132,12 +133,12 @@ impl From<Point> for Point3D {
 }
 
 impl FromStr for Point3D {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let points = s.split(",").map(|p| number::parse_isize(p)).collect::<Result<Vec<isize>, String>>()?;
+        let points = s.split(",").map(|p| number::parse_isize(p)).collect::<Result<Vec<isize>, Error>>()?;
         if points.len() != 3 {
-            Err(format!("Expected three coordinates, but got {}", points.len()))
+            Err(Error::parse(format!("Expected three coordinates, but got {}", points.len())))
         } else {
             Ok(Point3D { x: points[0], y: points[1], z: points[2] })
         }
@@ -363,10 +364,13 @@ impl Bounds {
     }
 }
 
+// A rectangular grid, with a value for every point within its bounds.
 #[derive(Eq, PartialEq, Clone, Default)]
 pub struct Grid<T> where T: Clone + Default {
     pub bounds: Bounds,
-    cells: HashMap<Point, T>,
+    pub topology: Topology,
+    // Row-major: the cell of a point is at (y - top) * width + (x - left).
+    cells: Vec<T>,
 }
 
 #[repr(u8)]
@@ -385,60 +389,140 @@ impl Directions {
     }
 }
 
+// A single step to one of the 8 neighbours of a point. Up is towards lower y.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    // Clockwise, starting at the top.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up, Direction::UpRight, Direction::Right, Direction::DownRight,
+        Direction::Down, Direction::DownLeft, Direction::Left, Direction::UpLeft,
+    ];
+
+    // The change in (x, y) of a step in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    fn is_in(&self, directions: &Directions) -> bool {
+        directions.has(match self {
+            Direction::Up | Direction::Down => Directions::Vertical,
+            Direction::Left | Direction::Right => Directions::Horizontal,
+            _ => Directions::Diagonal
+        })
+    }
+}
+
+// What happens at the edges of a grid: a bounded grid simply ends there, while on a toroidal grid, stepping off one
+// edge ends up at the opposite edge.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    Toroidal,
+}
+
 #[allow(unused)]
 impl<T> Grid<T> where T: Clone + Default {
+    // Creates the smallest grid containing all given cells. Points within the bounds without a cell get the default.
+    // Without any cells, the grid is empty.
     pub fn new(cells: HashMap<Point, T>) -> Self {
+        if cells.is_empty() {
+            return Self::with_bounds(Bounds::default());
+        }
+
         let points: Vec<_> = cells.keys().collect();
         let top = points.iter().map(|p| p.y).min().unwrap_or(0);
         let bottom = points.iter().map(|p| p.y).max().unwrap_or(0);
         let left = points.iter().map(|p| p.x).min().unwrap_or(0);
         let right = points.iter().map(|p| p.x).max().unwrap_or(0);
 
-        let bounds = Bounds::from_tlbr(top, left, bottom, right);
-        Self { bounds, cells }
+        let mut grid = Self::with_bounds(Bounds::from_tlbr(top, left, bottom, right));
+        for (point, value) in cells {
+            grid.set(point, value);
+        }
+        grid
+    }
+
+    // Creates a grid of the given bounds, filled with the default value.
+    pub fn with_bounds(bounds: Bounds) -> Self {
+        Self { bounds, topology: Topology::Bounded, cells: vec![T::default(); bounds.width * bounds.height] }
+    }
+
+    fn index(&self, p: &Point) -> Option<usize> {
+        if self.bounds.contains(p) {
+            Some((p.y - self.bounds.top) as usize * self.bounds.width + (p.x - self.bounds.left) as usize)
+        } else {
+            None
+        }
     }
 
     pub fn get(&self, p: &Point) -> Option<T> {
-        self.cells.get(p).map(|x| x.clone())
+        self.index(p).map(|i| self.cells[i].clone())
     }
 
     pub fn get_mut(&mut self, p: &Point) -> Option<&mut T> {
-        self.cells.get_mut(p)
+        self.index(p).map(move |i| &mut self.cells[i])
     }
 
-    pub fn set(&mut self, p: Point, v: T) {
-        self.cells.insert(p, v);
+    // Sets the value of a point of the grid. Points outside the bounds of the grid are ignored; the result tells whether
+    // the point was in the grid.
+    pub fn set(&mut self, p: Point, v: T) -> bool {
+        match self.index(&p) {
+            Some(i) => {
+                self.cells[i] = v;
+                true
+            }
+            None => false
+        }
     }
     
     pub fn get_adjacent(&self, p: &Point, directions: Directions) -> Vec<T> {
         self.get_adjacent_points(p, directions).iter().filter_map(|p| self.get(p)).collect()
     }
 
+    // The neighbours of the point in the given directions, clockwise starting at the top. On a bounded grid, neighbours
+    // outside the bounds are left out; on a toroidal grid, they wrap around (so on a grid only 1 or 2 cells wide, a point
+    // can be its own neighbour, or have the same neighbour twice).
     pub fn get_adjacent_points(&self, p: &Point, directions: Directions) -> Vec<Point> {
-        let mut points = vec![];
-
-        let x = p.x;
-        let y = p.y;
-
-        let include_vertical = directions.has(Directions::Vertical);
-        let include_horizontal = directions.has(Directions::Horizontal);
-        let include_diagonal = directions.has(Directions::Diagonal);
-
-        let include_top = y > self.bounds.top;
-        let include_bottom = y < self.bounds.bottom() - 1;
-        let include_left = x > self.bounds.left;
-        let include_right = x < self.bounds.right() - 1;
-
-        if include_top && include_horizontal { points.push((x, y - 1).into()) } // top
-        if include_top && include_right && include_diagonal { points.push((x + 1, y - 1).into()) } // top-right
-        if include_right && include_horizontal { points.push((x + 1, y).into()) } // right
-        if include_bottom && include_right && include_diagonal { points.push((x + 1, y + 1).into()) } // bottom-right
-        if include_bottom && include_vertical { points.push((x, y + 1).into()) } // bottom
-        if include_bottom && include_left && include_diagonal { points.push((x - 1, y + 1).into()) } // bottom-left
-        if include_left && include_horizontal { points.push((x - 1, y).into()) } // left
-        if include_left && include_top && include_diagonal { points.push((x - 1, y - 1).into()) } // top-left
-
-        points
+        Direction::ALL.iter()
+            .filter(|d| d.is_in(&directions))
+            .filter_map(|d| self.step(p, *d))
+            .collect()
+    }
+
+    // The point one step from the given one in the given direction, or None if that is outside a bounded grid.
+    pub fn step(&self, p: &Point, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.offset();
+        let target = Point { x: p.x + dx, y: p.y + dy };
+        match self.topology {
+            Topology::Bounded if self.bounds.contains(&target) => Some(target),
+            Topology::Bounded => None,
+            Topology::Toroidal if self.bounds.width == 0 || self.bounds.height == 0 => None,
+            Topology::Toroidal => Some(Point {
+                x: self.bounds.left + (target.x - self.bounds.left).rem_euclid(self.bounds.width as isize),
+                y: self.bounds.top + (target.y - self.bounds.top).rem_euclid(self.bounds.height as isize),
+            }),
+        }
     }
 
     pub fn points(&self) -> Vec<Point> {
@@ -453,12 +537,13 @@ impl<T> Grid<T> where T: Clone + Default {
         points
     }
 
+    // The values of all cells, row by row.
     pub fn values(&self) -> Vec<T> {
-        self.points().iter().map(|p| self.get(p).unwrap_or_default()).collect()
+        self.cells.clone()
     }
-    
+
     pub fn entries(&self) -> Vec<(Point, T)> {
-        self.cells.iter().map(|(p, t)| (p.clone(), t.clone())).collect()
+        self.points().into_iter().zip(self.cells.iter().cloned()).collect()
     }
 }
 
@@ -478,7 +563,7 @@ impl<T> fmt::Display for Grid<T> where T: fmt::Display + Clone + Default {
         for y in self.bounds.y() {
             let mut line = vec![];
             for x in self.bounds.x() {
-                line.push(self.cells.get(&(x, y).into()).map(|v| format!("{}", v)).unwrap_or(String::new()))
+                line.push(self.get(&(x, y).into()).map(|v| format!("{}", v)).unwrap_or(String::new()))
             }
             lines.push(line);
         }
@@ -496,17 +581,19 @@ impl<T> fmt::Display for Grid<T> where T: fmt::Display + Clone + Default {
 }
 
 impl<T> FromStr for Grid<T> where T: FromStr + Clone + Default {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parse_result: Result<Vec<Vec<T>>, String> = s.lines()
-            .filter(|l| !l.is_empty())
-            .map(|l| l.chars().map(|c|
-                String::from(c).parse::<T>().map_err(|_| format!("Could not parse '{}' to {}", c, std::any::type_name::<T>())))
-                .collect::<Result<Vec<T>, String>>())
+        let parse_result: Result<Vec<(usize, Vec<T>)>, Error> = s.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.is_empty())
+            .map(|(y, l)| l.chars().enumerate().map(|(x, c)|
+                String::from(c).parse::<T>().map_err(|_| Error::parse(format!("Could not parse '{}' to {}", c, std::any::type_name::<T>())).at(y + 1, x + 1)))
+                .collect::<Result<Vec<T>, Error>>()
+                .map(|row| (y, row)))
             .collect();
 
-        let cells = match parse_result {
+        let lines = match parse_result {
             Ok(lines) if lines.len() == 0 => {
                 return Ok(Grid::default());
             }
@@ -514,12 +601,17 @@ impl<T> FromStr for Grid<T> where T: FromStr + Clone + Default {
             Err(e) => return Err(e)
         };
 
-        Grid::try_from(cells)
+        let width = lines[0].1.len();
+        if let Some((y, row)) = lines.iter().find(|(_, row)| row.len() != width) {
+            return Err(Error::parse(format!("Expected a line of width {}, but got {} instead", width, row.len())).at(y + 1, width.min(row.len()) + 1));
+        }
+
+        Grid::try_from(lines.into_iter().map(|(_, row)| row).collect::<Vec<_>>())
     }
 }
 
 impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> where T: Clone + Default {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(data: Vec<Vec<T>>) -> Result<Self, Self::Error> {
         let height = data.len();
@@ -528,23 +620,94 @@ impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> where T: Clone + Default {
         let bounds = Bounds { top: 0, left: 0, width, height };
 
         if data.iter().all(|l| l.len() == width) {
-            let mut cells = HashMap::new();
-            for y in 0..height {
-                for x in 0..width {
-                    cells.insert((x, y).try_into().unwrap(), data[y][x].clone());
-                }
-            }
-
-            Ok(Grid { bounds, cells })
+            Ok(Grid { bounds, topology: Topology::Bounded, cells: data.into_iter().flatten().collect() })
         } else {
-            Err(format!("Not all lines in input are the same width"))
+            Err(Error::parse("Not all lines in input are the same width"))
         }
     }
 }
 
+// A grid without fixed bounds: only the cells that were set are stored, and every other point has the background
+// value. The bounds grow to include every point that is set.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SparseGrid<T> where T: Clone {
+    background: T,
+    bounds: Option<Bounds>,
+    cells: HashMap<Point, T>,
+}
+
+#[allow(unused)]
+impl<T> SparseGrid<T> where T: Clone {
+    pub fn new(background: T) -> Self {
+        Self { background, bounds: None, cells: HashMap::new() }
+    }
+
+    // The smallest bounds containing every cell that was set, or empty bounds if none was.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds.unwrap_or(Bounds { top: 0, left: 0, width: 0, height: 0 })
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, p: &Point) -> T {
+        self.cells.get(p).unwrap_or(&self.background).clone()
+    }
+
+    pub fn set(&mut self, p: Point, v: T) {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) if bounds.contains(&p) => bounds,
+            Some(bounds) => Bounds::from_tlbr(
+                bounds.top.min(p.y), bounds.left.min(p.x),
+                (bounds.bottom() - 1).max(p.y), (bounds.right() - 1).max(p.x)),
+            None => Bounds::from_tlbr(p.y, p.x, p.y, p.x)
+        });
+        self.cells.insert(p, v);
+    }
+
+    // The number of cells that were set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The cells that were set, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+
+    // A view of the given part of the grid, which displays every cell as the character given by `render`. The window
+    // may extend beyond the bounds of the grid, showing the background there.
+    pub fn window<F>(&self, bounds: Bounds, render: F) -> Window<'_, T, F> where F: Fn(&T) -> char {
+        Window { grid: self, bounds, render }
+    }
+}
+
+pub struct Window<'a, T, F> where T: Clone, F: Fn(&T) -> char {
+    grid: &'a SparseGrid<T>,
+    bounds: Bounds,
+    render: F,
+}
+
+impl<T, F> fmt::Display for Window<'_, T, F> where T: Clone, F: Fn(&T) -> char {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.bounds.y()
+            .map(|y| self.bounds.x().map(|x| (self.render)(&self.grid.get(&(x, y).into()))).collect())
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
 #[cfg(test)]
 mod grid_tests {
-    use crate::util::geometry::{Grid, Directions};
+    use std::collections::HashMap;
+    use crate::error::Error;
+    use crate::util::geometry::{Bounds, Direction, Grid, Directions, Topology};
 
     const EXAMPLE_GRID_INPUT: &str = "\
         2199943210\n\
@@ -593,6 +756,13 @@ mod grid_tests {
         assert_eq!(EXAMPLE_GRID_INPUT.parse::<Grid<usize>>(), Ok(get_example_grid()));
     }
 
+    #[test]
+    fn test_grid_from_str_errors() {
+        assert_eq!("123\n4x6\n789".parse::<Grid<usize>>(), Err(Error::parse("Could not parse 'x' to usize").at(2, 2)));
+        assert_eq!("123\n\n45\n789".parse::<Grid<usize>>(), Err(Error::parse("Expected a line of width 3, but got 2 instead").at(3, 3)));
+        assert_eq!("123\n4567".parse::<Grid<usize>>(), Err(Error::parse("Expected a line of width 3, but got 4 instead").at(2, 4)));
+    }
+
     #[test]
     fn test_get_adjacent() {
         let grid = get_example_grid();
@@ -616,9 +786,116 @@ mod grid_tests {
                    vec![(5, 2).into(), (6, 2).into(), (6, 3).into(), (6, 4).into(), (5, 4).into(), (4, 4).into(), (4, 3).into(), (4, 2).into()]);
     }
 
+    #[test]
+    fn test_get_adjacent_points_toroidal() {
+        let mut grid = get_example_grid();
+        grid.topology = Topology::Toroidal;
+
+        assert_eq!(grid.get_adjacent_points(&(0, 0).into(), Directions::NonDiagonal),
+                   vec![(0, 4).into(), (1, 0).into(), (0, 1).into(), (9, 0).into()]);
+        assert_eq!(grid.get_adjacent(&(9, 4).into(), Directions::All), vec![9, 8, 9, 2, 0, 1, 7, 8]);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut grid = get_example_grid();
+        assert_eq!(grid.step(&(5, 3).into(), Direction::DownLeft), Some((4, 4).into()));
+        assert_eq!(grid.step(&(9, 2).into(), Direction::Right), None);
+        assert_eq!(grid.step(&(0, 0).into(), Direction::UpLeft), None);
+
+        grid.topology = Topology::Toroidal;
+        assert_eq!(grid.step(&(5, 3).into(), Direction::DownLeft), Some((4, 4).into()));
+        assert_eq!(grid.step(&(9, 2).into(), Direction::Right), Some((0, 2).into()));
+        assert_eq!(grid.step(&(0, 0).into(), Direction::UpLeft), Some((9, 4).into()));
+    }
+
     #[test]
     fn test_values() {
         let grid: Grid<usize> = vec![vec![1, 2, 3], vec![9, 8, 7], vec![5, 6, 4]].try_into().unwrap();
         assert_eq!(grid.values(), vec![1, 2, 3, 9, 8, 7, 5, 6, 4]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_entries() {
+        let grid: Grid<usize> = vec![vec![1, 2], vec![3, 4]].try_into().unwrap();
+        assert_eq!(grid.entries(), vec![((0, 0).into(), 1), ((1, 0).into(), 2), ((0, 1).into(), 3), ((1, 1).into(), 4)]);
+    }
+
+    #[test]
+    fn test_new() {
+        // The bounds do not have to start at 0, and points without a cell get the default value.
+        let grid = Grid::new(HashMap::from([((-1, 2).into(), 5), ((1, 3).into(), 7)]));
+        assert_eq!(grid.bounds, Bounds { top: 2, left: -1, width: 3, height: 2 });
+        assert_eq!(grid.values(), vec![5, 0, 0, 0, 0, 7]);
+        assert_eq!(grid.get(&(1, 3).into()), Some(7));
+        assert_eq!(grid.get(&(2, 3).into()), None);
+
+        let empty: Grid<usize> = Grid::new(HashMap::new());
+        assert_eq!(empty.bounds, Bounds::default());
+        assert_eq!(empty.values(), Vec::<usize>::new());
+        assert_eq!(empty.get(&(0, 0).into()), None);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid: Grid<usize> = Grid::with_bounds(Bounds { top: -1, left: -1, width: 3, height: 3 });
+        assert!(grid.set((0, 0).into(), 5));
+        *grid.get_mut(&(1, 1).into()).unwrap() += 2;
+        assert_eq!(grid.values(), vec![0, 0, 0, 0, 5, 0, 0, 0, 2]);
+        assert_eq!(grid.get_mut(&(2, 2).into()), None);
+    }
+
+    #[test]
+    fn test_set_out_of_bounds() {
+        let mut grid: Grid<usize> = vec![vec![1, 2, 3]].try_into().unwrap();
+        assert!(!grid.set((3, 0).into(), 4));
+        assert_eq!(grid.values(), vec![1, 2, 3]);
+    }
+}
+
+#[cfg(test)]
+mod sparse_grid_tests {
+    use crate::util::geometry::{Bounds, SparseGrid};
+
+    fn render(v: &bool) -> char {
+        if *v { '#' } else { '.' }
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut grid = SparseGrid::new(false);
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), Bounds { top: 0, left: 0, width: 0, height: 0 });
+
+        grid.set((2, 3).into(), true);
+        assert_eq!(grid.bounds(), Bounds { top: 3, left: 2, width: 1, height: 1 });
+        grid.set((-1, 4).into(), false);
+        assert_eq!(grid.bounds(), Bounds { top: 3, left: -1, width: 4, height: 2 });
+
+        assert!(grid.get(&(2, 3).into()));
+        assert!(!grid.get(&(-1, 4).into()));
+        assert!(!grid.get(&(100, -100).into()));
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_background() {
+        let mut grid = SparseGrid::new(true);
+        grid.set((0, 0).into(), false);
+        assert!(grid.get(&(5, 5).into()));
+        assert_eq!(grid.background(), &true);
+    }
+
+    #[test]
+    fn test_window() {
+        let mut grid = SparseGrid::new(false);
+        grid.set((0, 0).into(), true);
+        grid.set((2, 1).into(), true);
+
+        assert_eq!(format!("{}", grid.window(grid.bounds(), render)), "#..\n..#");
+
+        let mut bounds = grid.bounds();
+        bounds.grow(1);
+        assert_eq!(format!("{}", grid.window(bounds, render)), ".....\n.#...\n...#.\n.....");
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{create_dir_all, read_to_string, remove_file, rename, write};
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+use crate::error::Error;
+use crate::util::create_day::{staged_path, PLACEHOLDER_INPUT};
+use crate::util::input::year_dir;
+
+const CHECKSUMS_FILE: &str = "checksums.json";
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum Import {
+    // The input was written to the given path.
+    Imported(PathBuf),
+    // The input at the given path already had this content; only its checksum was recorded.
+    Unchanged(PathBuf),
+}
+
+// Normalises a puzzle input: Windows and old Mac line endings become '\n', and the input ends in exactly one newline.
+pub fn normalize_input(input: &str) -> String {
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+    format!("{}\n", input.trim_end_matches('\n'))
+}
+
+// SHA-256 of the given input, as a hex string.
+pub fn checksum(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Copies the given input into resources/<year>/dayNN.txt, and records its checksum. An existing input with different
+// content (other than the placeholder written by `add`, or the same input before normalising) is only overwritten when
+// forced.
+pub fn import_input(year: i32, day: i32, input: &str, force: bool) -> Result<Import, String> {
+    import_into(&year_dir(year), year, day, input, force)
+}
+
+// Imports into the given resources folder of a year.
+fn import_into(dir: &Path, year: i32, day: i32, input: &str, force: bool) -> Result<Import, String> {
+    let input = normalize_input(input);
+    if input.trim().is_empty() {
+        return Err(format!("Refusing to import an empty input for day {} of {}", day, year));
+    }
+
+    let path = dir.join(format!("day{:02}.txt", day));
+    let unchanged = match read_to_string(&path) {
+        Ok(existing) if existing == input => true,
+        Ok(existing) if normalize_input(&existing) == input || existing == PLACEHOLDER_INPUT || force => false,
+        Ok(_) => return Err(format!("A different input for day {} of {} already exists: {}\nUse --force to overwrite it.", day, year, path.display())),
+        Err(_) => false
+    };
+
+    let mut checksums = read_checksums_in(dir).map_err(|e| e.to_string())?;
+    checksums.entries.insert(day, checksum(&input));
+
+    // The input and its checksum are written to temporary files first, and only moved into place once both were written,
+    // so a failed write leaves neither of them changed.
+    let mut files = vec![(dir.join(CHECKSUMS_FILE), checksums.to_string())];
+    if !unchanged {
+        files.insert(0, (path.clone(), input));
+    }
+    create_dir_all(dir).map_err(|e| format!("Could not create resources folder: {:?}\nError: {}", dir, e))?;
+    let result = write_staged(&files).and_then(|_| files.iter()
+        .try_for_each(|(path, _)| rename(staged_path(path), path).map_err(|e| format!("Could not write file: {:?}\nError: {}", path, e))));
+    if result.is_err() {
+        for (path, _) in &files {
+            let _ = remove_file(staged_path(path));
+        }
+    }
+    result?;
+
+    Ok(if unchanged { Import::Unchanged(path) } else { Import::Imported(path) })
+}
+
+fn write_staged(files: &[(PathBuf, String)]) -> Result<(), String> {
+    files.iter().try_for_each(|(path, content)| {
+        let staged = staged_path(path);
+        write(&staged, content).map_err(|e| format!("Could not write file: {:?}\nError: {}", staged, e))
+    })
+}
+
+// Checksums of the imported inputs of a year, keyed by day. Every year has its own file, which looks like:
+// { "1": "<sha256 of resources/<year>/day01.txt>", ... }
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct Checksums {
+    entries: HashMap<i32, String>,
+}
+
+impl Checksums {
+    pub fn get(&self, day: i32) -> Option<&String> {
+        self.entries.get(&day)
+    }
+
+    // Fails if a checksum was recorded for the day, and the given input does not match it.
+    pub fn verify(&self, year: i32, day: i32, input: &str) -> Result<(), Error> {
+        match self.get(day) {
+            Some(expected) if expected != &checksum(input) => Err(Error::invalid_state(format!(
+                "The input for day {} of {} was changed after it was imported; import it again with --force if that was intended",
+                day, year))),
+            _ => Ok(())
+        }
+    }
+}
+
+impl TryFrom<&str> for Checksums {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let raw: HashMap<String, String> = serde_json::from_str(value)
+            .map_err(|e| Error::parse(format!("Could not parse checksums: {}", e)).at(e.line(), e.column()))?;
+
+        let mut entries = HashMap::new();
+        for (day, checksum) in raw {
+            let day_number = day.parse::<i32>().map_err(|_| Error::parse(format!("Invalid day '{}' in checksums", day)))?;
+            entries.insert(day_number, checksum);
+        }
+
+        Ok(Checksums { entries })
+    }
+}
+
+// Sorted by day, in the same layout as answers.json.
+impl fmt::Display for Checksums {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut days: Vec<&i32> = self.entries.keys().collect();
+        days.sort();
+        let lines: Vec<String> = days.into_iter().map(|day| format!("  \"{}\": \"{}\"", day, self.entries[day])).collect();
+        writeln!(f, "{{\n{}\n}}", lines.join(",\n"))
+    }
+}
+
+// The recorded checksums of the given year, or none if nothing was imported for it yet.
+pub fn read_checksums(year: i32) -> Result<Checksums, Error> {
+    read_checksums_in(&year_dir(year))
+}
+
+fn read_checksums_in(dir: &Path) -> Result<Checksums, Error> {
+    match read_to_string(dir.join(CHECKSUMS_FILE)) {
+        Ok(content) => Checksums::try_from(content.as_str()),
+        Err(_) => Ok(Checksums::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all, write};
+    use std::path::PathBuf;
+    use crate::error::Error;
+    use crate::util::create_day::PLACEHOLDER_INPUT;
+    use crate::util::import::{checksum, import_into, normalize_input, read_checksums_in, Checksums, Import};
+
+    fn get_temp_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("aoc-import-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&dir);
+        dir
+    }
+
+    fn file_names(dir: &PathBuf) -> Vec<String> {
+        let mut names: Vec<String> = read_dir(dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().to_string()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_import_into() {
+        let dir = get_temp_dir("import");
+        let path = dir.join("day03.txt");
+
+        assert_eq!(import_into(&dir, 2021, 3, "1\r\n2\r\n", false), Ok(Import::Imported(path.clone())));
+        assert_eq!(read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(read_checksums_in(&dir).unwrap().get(3), Some(&checksum("1\n2\n")));
+        assert_eq!(file_names(&dir), vec!["checksums.json", "day03.txt"]);
+
+        assert_eq!(import_into(&dir, 2021, 3, "1\n2", false), Ok(Import::Unchanged(path.clone())));
+        assert!(import_into(&dir, 2021, 3, "1\n3\n", false).unwrap_err().starts_with("A different input for day 3 of 2021 already exists"));
+        assert_eq!(read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(import_into(&dir, 2021, 3, "1\n3\n", true), Ok(Import::Imported(path.clone())));
+        assert_eq!(read_checksums_in(&dir).unwrap().get(3), Some(&checksum("1\n3\n")));
+
+        // The placeholder written by `add` is always replaced.
+        write(dir.join("day04.txt"), PLACEHOLDER_INPUT).unwrap();
+        assert_eq!(import_into(&dir, 2021, 4, "4\n", false), Ok(Import::Imported(dir.join("day04.txt"))));
+        assert_eq!(read_checksums_in(&dir).unwrap().get(3), Some(&checksum("1\n3\n")));
+
+        assert_eq!(import_into(&dir, 2021, 5, "\n\n", false), Err("Refusing to import an empty input for day 5 of 2021".to_string()));
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_into_failure() {
+        let dir = get_temp_dir("import-failure");
+        assert_eq!(import_into(&dir, 2021, 3, "1\n", false), Ok(Import::Imported(dir.join("day03.txt"))));
+
+        // Writing the checksums fails, so the input is not changed either.
+        create_dir_all(dir.join(".checksums.json.tmp")).unwrap();
+        assert!(import_into(&dir, 2021, 3, "2\n", true).unwrap_err().starts_with("Could not write file"));
+        assert_eq!(read_to_string(dir.join("day03.txt")).unwrap(), "1\n");
+        assert_eq!(read_checksums_in(&dir).unwrap().get(3), Some(&checksum("1\n")));
+        assert_eq!(file_names(&dir), vec![".checksums.json.tmp", "checksums.json", "day03.txt"]);
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_input() {
+        assert_eq!(normalize_input("1\r\n2\r\n"), "1\n2\n");
+        assert_eq!(normalize_input("1\r2"), "1\n2\n");
+        assert_eq!(normalize_input("1\n2\n\n\n"), "1\n2\n");
+        // Blank lines within the input are part of it, e.g. between the sections of day 4.
+        assert_eq!(normalize_input("1\n\n2"), "1\n\n2\n");
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_ne!(checksum("1\n2\n"), checksum("1\n3\n"));
+    }
+
+    #[test]
+    fn test_checksums() {
+        let content = format!("{{\n  \"2\": \"{}\",\n  \"10\": \"{}\"\n}}\n", checksum("2\n"), checksum("10\n"));
+        let checksums = Checksums::try_from(content.as_str()).unwrap();
+        assert_eq!(checksums.get(2), Some(&checksum("2\n")));
+        assert_eq!(checksums.to_string(), content);
+
+        assert_eq!(checksums.verify(2021, 2, "2\n"), Ok(()));
+        assert_eq!(checksums.verify(2021, 3, "anything"), Ok(()));
+        assert_eq!(checksums.verify(2021, 10, "11\n"), Err(Error::invalid_state(
+            "The input for day 10 of 2021 was changed after it was imported; import it again with --force if that was intended")));
+
+        assert!(Checksums::try_from(r#"{ "one": "abc" }"#).is_err());
+    }
+}
@@ -1,5 +1,145 @@
-use std::fs::read_to_string;
+use std::fs::{read_dir, read_to_string};
+use std::io::{stdin, Read};
+use std::path::PathBuf;
+use crate::error::Error;
+use crate::util::import::read_checksums;
 
-pub fn read_input(day: i32) -> Result<String, String> {
-    read_to_string(format!("resources/day{:02}.txt", day)).map_err(|e| format!("{}", e))
+const DEFAULT_EXAMPLE: &str = "default";
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum InputSource {
+    // The real puzzle input for the given year and day, from the resources folder of that year. If the input was
+    // imported, reading it fails when it no longer matches its recorded checksum.
+    Day(i32, i32),
+    // An example input for the given year and day, from the examples folder of that year. Without a name this is the
+    // default example (dayNN.txt), otherwise the named one (dayNN-name.txt).
+    Example(i32, i32, Option<String>),
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    // Interprets a command line path, where '-' means stdin.
+    pub fn from_path(path: &str) -> Self {
+        match path {
+            "-" => InputSource::Stdin,
+            _ => InputSource::File(PathBuf::from(path))
+        }
+    }
+
+    // Interprets a command line example name, where 'default' means the default example of the day.
+    pub fn example(year: i32, day: i32, name: &str) -> Self {
+        match name {
+            DEFAULT_EXAMPLE => InputSource::Example(year, day, None),
+            _ => InputSource::Example(year, day, Some(name.to_string()))
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            InputSource::Day(year, day) => {
+                let path = year_dir(*year).join(format!("day{:02}.txt", day));
+                let input = read_to_string(&path).map_err(|e| Error::MissingInput(format!("Could not read input for day {} of {} ({}): {}", day, year, path.display(), e)))?;
+                read_checksums(*year)?.verify(*year, *day, &input)?;
+                Ok(input)
+            }
+            InputSource::Example(year, day, name) => {
+                let file_name = match name {
+                    Some(name) => format!("day{:02}-{}.txt", day, name),
+                    None => format!("day{:02}.txt", day)
+                };
+                read_to_string(examples_dir(*year).join(file_name)).map_err(|_| Error::MissingInput(format!(
+                    "No example '{}' for day {} of {}, available: {}",
+                    name.as_deref().unwrap_or(DEFAULT_EXAMPLE), day, year, example_names(*year, *day).join(", "))))
+            }
+            InputSource::File(path) => {
+                read_to_string(path).map_err(|e| Error::MissingInput(format!("Could not read '{}': {}", path.display(), e)))
+            }
+            InputSource::Stdin => {
+                let mut input = String::new();
+                stdin().read_to_string(&mut input).map_err(|e| Error::MissingInput(format!("Could not read stdin: {}", e)))?;
+                Ok(input)
+            }
+            InputSource::Inline(input) => Ok(input.clone())
+        }
+    }
+}
+
+// The resources folder of this crate, so the binary does not depend on the working directory.
+pub fn resources_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources")
+}
+
+// The resources of a single year: its inputs, examples and answers.
+pub fn year_dir(year: i32) -> PathBuf {
+    resources_dir().join(year.to_string())
+}
+
+pub fn examples_dir(year: i32) -> PathBuf {
+    year_dir(year).join("examples")
+}
+
+// The names of the examples available for the given day, sorted.
+pub fn example_names(year: i32, day: i32) -> Vec<String> {
+    let prefix = format!("day{:02}", day);
+    let mut names: Vec<String> = read_dir(examples_dir(year)).into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file_name| match file_name.strip_prefix(&prefix)?.strip_suffix(".txt")? {
+            "" => Some(DEFAULT_EXAMPLE.to_string()),
+            name => name.strip_prefix('-').map(|n| n.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn read_input(year: i32, day: i32) -> Result<String, Error> {
+    InputSource::Day(year, day).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use crate::util::input::{example_names, InputSource, resources_dir};
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(InputSource::from_path("-"), InputSource::Stdin);
+        assert_eq!(InputSource::from_path("some/input.txt"), InputSource::File(PathBuf::from("some/input.txt")));
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(InputSource::example(2021, 12, "default"), InputSource::Example(2021, 12, None));
+        assert_eq!(InputSource::example(2021, 12, "large"), InputSource::Example(2021, 12, Some("large".to_string())));
+    }
+
+    #[test]
+    fn test_example_names() {
+        assert_eq!(example_names(2021, 1), vec!["default"]);
+        assert_eq!(example_names(2021, 12), vec!["huge", "large", "small"]);
+        assert_eq!(example_names(2021, 24), Vec::<String>::new());
+        assert_eq!(example_names(2015, 1), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_read_example() {
+        assert!(InputSource::Example(2021, 1, None).read().unwrap().starts_with("199\n"));
+        assert!(InputSource::Example(2021, 12, Some("small".to_string())).read().unwrap().starts_with("start-A\n"));
+        assert_eq!(InputSource::Example(2021, 12, None).read().map_err(|e| e.to_string()),
+                   Err("Missing input: No example 'default' for day 12 of 2021, available: huge, large, small".to_string()));
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(InputSource::Inline("1\n2\n".to_string()).read(), Ok("1\n2\n".to_string()));
+
+        let template = resources_dir().join("templates").join("default.rs.template");
+        assert!(InputSource::File(template).read().unwrap().contains("{{solver}}"));
+        assert!(InputSource::File(PathBuf::from("does/not/exist.txt")).read().is_err());
+        assert!(InputSource::Day(2021, 1).read().is_ok());
+        assert!(InputSource::Day(2015, 1).read().is_err());
+    }
 }
@@ -1,8 +1,10 @@
+use crate::error::Error;
+
 macro_rules! parse_int_impl {
     ($($t:ty, $name: ident)*) => {$(
         #[allow(unused)]
-        pub fn $name(input: &str) -> Result<$t, String> {
-            input.to_string().parse().map_err(|e| format!("{}", e))
+        pub fn $name(input: &str) -> Result<$t, Error> {
+            input.parse().map_err(|e| Error::parse(format!("Invalid {} '{}': {}", stringify!($t), input, e)))
         }
     )*}
 }
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use crate::error::panic_message;
+
+// The number of threads to use when none is given: one per core.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Runs `f` on every item on up to `jobs` threads, and hands the results to `on_result` in the order of the items. A
+// result is handed over as soon as it and all results before it are available, so the output can be shown while slow
+// items are still running. When `f` panics on an item, its result is the panic message instead, and the other items
+// still run.
+pub fn for_each_ordered<T, R, F, C>(items: Vec<T>, jobs: usize, f: F, mut on_result: C)
+    where T: Send, R: Send, F: Fn(T) -> R + Sync, C: FnMut(Result<R, String>)
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                // Take the next item, and release the lock before working on it.
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some((index, item)) => {
+                        let result = catch_unwind(AssertUnwindSafe(|| f(item))).map_err(|p| panic_message(p.as_ref()));
+                        if sender.send((index, result)).is_err() { break; }
+                    }
+                    None => break
+                }
+            });
+        }
+        // Only the workers hold a sender now, so the loop below ends once they are all done.
+        drop(sender);
+
+        let mut pending = HashMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                on_result(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+    use std::time::Duration;
+    use crate::util::parallel::for_each_ordered;
+
+    #[test]
+    fn test_for_each_ordered() {
+        for jobs in [1, 3, 16] {
+            let mut results = vec![];
+            // Earlier items take longer, so they finish last when running in parallel.
+            for_each_ordered((0..10).collect(), jobs, |i: u64| {
+                sleep(Duration::from_millis(10 - i));
+                i * i
+            }, |r| results.push(r));
+            assert_eq!(results, (0..10).map(|i| Ok(i * i)).collect::<Vec<_>>(), "jobs: {}", jobs);
+
+            // A panicking item does not stop the others.
+            let mut results = vec![];
+            for_each_ordered((0..4).collect(), jobs, |i: u64| {
+                if i == 1 { panic!("item {} failed", i) }
+                i * i
+            }, |r| results.push(r));
+            assert_eq!(results, vec![Ok(0), Err("item 1 failed".to_string()), Ok(4), Ok(9)], "jobs: {}", jobs);
+        }
+    }
+
+    #[test]
+    fn test_for_each_ordered_empty() {
+        let mut results: Vec<Result<i32, String>> = vec![];
+        for_each_ordered(vec![], 4, |i: i32| i, |r| results.push(r));
+        assert!(results.is_empty());
+    }
+}
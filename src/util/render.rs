@@ -0,0 +1,72 @@
+use crate::util::geometry::Grid;
+
+pub type Rgb = [u8; 3];
+
+// A single cell of a picture: the character to print it as, and the colour to draw it in.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Pixel {
+    pub symbol: char,
+    pub color: Rgb,
+    // Highlighted pixels stand out when printed, e.g. the cells on a path.
+    pub highlight: bool,
+}
+
+impl Default for Pixel {
+    fn default() -> Self {
+        Pixel { symbol: ' ', color: [0, 0, 0], highlight: false }
+    }
+}
+
+// A picture of (part of) a puzzle, e.g. a map with the path that was taken through it, for the render command.
+pub type Picture = Grid<Pixel>;
+
+// The symbols of the picture, row by row. With `ansi`, highlighted pixels are shown in bold red (using ANSI escape
+// codes); otherwise, e.g. when the text is written to a file, they are shown as '*' instead.
+pub fn to_text(picture: &Picture, ansi: bool) -> String {
+    let lines: Vec<String> = picture.bounds.y()
+        .map(|y| picture.bounds.x()
+            .map(|x| match picture.get(&(x, y).into()).unwrap_or_default() {
+                Pixel { symbol, highlight: true, .. } if ansi => format!("\x1b[1;31m{}\x1b[0m", symbol),
+                Pixel { highlight: true, .. } => "*".to_string(),
+                Pixel { symbol, .. } => symbol.to_string()
+            })
+            .collect())
+        .collect();
+    lines.join("\n")
+}
+
+// The picture as a binary PPM image, with one image pixel per pixel. Most image viewers can open these, and they are
+// easily converted to e.g. PNG.
+pub fn to_ppm(picture: &Picture) -> Vec<u8> {
+    let mut result = format!("P6\n{} {}\n255\n", picture.bounds.width, picture.bounds.height).into_bytes();
+    // The values of a grid are in row-major order, as in the image.
+    result.extend(picture.values().iter().flat_map(|pixel| pixel.color));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::geometry::Grid;
+    use crate::util::render::{to_ppm, to_text, Picture, Pixel};
+
+    fn get_example_picture() -> Picture {
+        let pixel = |symbol, highlight| Pixel { symbol, color: if highlight { [255, 0, 0] } else { [1, 2, 3] }, highlight };
+        Grid::try_from(vec![
+            vec![pixel('1', true), pixel('2', false)],
+            vec![pixel('3', false), pixel('4', true)],
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(to_text(&get_example_picture(), true), "\x1b[1;31m1\x1b[0m2\n3\x1b[1;31m4\x1b[0m");
+        assert_eq!(to_text(&get_example_picture(), false), "*2\n3*");
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 0, 0, 1, 2, 3, 1, 2, 3, 255, 0, 0]);
+        assert_eq!(to_ppm(&get_example_picture()), expected);
+    }
+}
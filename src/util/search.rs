@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use crate::error::Error;
+use crate::util::cancel;
+
+// Generic searches over any kind of state. Each search starts at a single state, gets the states reachable in one step
+// (with the cost of that step) from `successors`, and stops at the first state for which `is_goal` holds. All of them
+// check for cancellation while searching, so they stop with Error::TimedOut when the puzzle runs out of time.
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SearchResult<S> {
+    // The total cost of all steps on the path.
+    pub cost: usize,
+    // The states from the start up to and including the goal.
+    pub path: Vec<S>,
+}
+
+// Breadth-first search: finds a path with the fewest steps. The costs of the steps are not considered, so the cost of
+// the result is the number of steps.
+pub fn bfs<S, N, I, G>(start: S, mut successors: N, mut is_goal: G) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, G: FnMut(&S) -> bool
+{
+    let mut nodes = vec![Node { state: start.clone(), parent: None, cost: 0 }];
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        cancel::check()?;
+        if is_goal(&nodes[index].state) {
+            return Ok(Some(reconstruct(nodes, index)));
+        }
+
+        for (next, _) in successors(&nodes[index].state) {
+            if seen.insert(next.clone()) {
+                nodes.push(Node { state: next, parent: Some(index), cost: nodes[index].cost + 1 });
+                queue.push_back(nodes.len() - 1);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+// Dijkstra's algorithm: finds a path with the lowest total cost.
+pub fn dijkstra<S, N, I, G>(start: S, successors: N, is_goal: G) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, G: FnMut(&S) -> bool
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+// Dijkstra's algorithm for states that can be numbered, e.g. the points of a grid: `index` maps every state to a
+// distinct number below `capacity`. This keeps track of the states in a Vec instead of a HashMap, which is a lot faster
+// for searches that visit many states.
+pub fn dijkstra_indexed<S, N, I, G, X>(start: S, successors: N, is_goal: G, index: X, capacity: usize) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, G: FnMut(&S) -> bool, X: Fn(&S) -> usize
+{
+    astar_indexed(start, successors, |_| 0, is_goal, index, capacity)
+}
+
+// A*: finds a path with the lowest total cost, exploring the states that look closest to a goal first. The heuristic
+// estimates the remaining cost from a state to a goal, and must never overestimate it, or the path may not be the
+// cheapest.
+pub fn astar<S, N, I, H, G>(start: S, successors: N, heuristic: H, is_goal: G) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, H: FnMut(&S) -> usize, G: FnMut(&S) -> bool
+{
+    search(start, successors, heuristic, is_goal, HashMap::new(), 0)
+}
+
+// A* for states that can be numbered, see `dijkstra_indexed`.
+pub fn astar_indexed<S, N, I, H, G, X>(start: S, successors: N, heuristic: H, is_goal: G, index: X, capacity: usize) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, H: FnMut(&S) -> usize, G: FnMut(&S) -> bool, X: Fn(&S) -> usize
+{
+    search(start, successors, heuristic, is_goal, Indexed(index), capacity)
+}
+
+// Numbers the states A* comes across, so it can keep track of them in a Vec.
+trait Slots<S> {
+    fn slot(&mut self, state: &S) -> usize;
+}
+
+// Any state can be numbered in the order in which it is first seen.
+impl<S: Clone + Eq + Hash> Slots<S> for HashMap<S, usize> {
+    fn slot(&mut self, state: &S) -> usize {
+        match self.get(state) {
+            Some(slot) => *slot,
+            None => {
+                let slot = self.len();
+                self.insert(state.clone(), slot);
+                slot
+            }
+        }
+    }
+}
+
+struct Indexed<X>(X);
+
+impl<S, X: Fn(&S) -> usize> Slots<S> for Indexed<X> {
+    fn slot(&mut self, state: &S) -> usize {
+        (self.0)(state)
+    }
+}
+
+fn search<S, N, I, H, G, L>(start: S, mut successors: N, mut heuristic: H, mut is_goal: G, mut slots: L, capacity: usize) -> Result<Option<SearchResult<S>>, Error>
+    where S: Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, H: FnMut(&S) -> usize, G: FnMut(&S) -> bool, L: Slots<S>
+{
+    // `best` holds the cheapest way found to every state, by slot, with the slot of the state it came from as parent. The
+    // heap refers to states by slot, so the states themselves don't need to be ordered; an entry of the heap is outdated
+    // once a cheaper way to its state has been found.
+    let mut best: Vec<Option<Node<S>>> = Vec::new();
+    best.resize_with(capacity, || None);
+    let start_slot = slots.slot(&start);
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&start)), 0, start_slot)]);
+    set_best(&mut best, start_slot, Node { state: start, parent: None, cost: 0 });
+
+    while let Some((_, cost, slot)) = queue.pop() {
+        cancel::check()?;
+        let node = match &best[slot] {
+            Some(node) if node.cost == cost => node,
+            _ => continue // A cheaper way to this state was found after this one was queued.
+        };
+        if is_goal(&node.state) {
+            return Ok(Some(reconstruct_best(best, slot)));
+        }
+
+        for (next, step_cost) in successors(&node.state) {
+            let next_cost = cost + step_cost;
+            let next_slot = slots.slot(&next);
+            if best.get(next_slot).and_then(|n| n.as_ref()).is_some_and(|n| n.cost <= next_cost) {
+                continue;
+            }
+            queue.push((Reverse(next_cost + heuristic(&next)), next_cost, next_slot));
+            set_best(&mut best, next_slot, Node { state: next, parent: Some(slot), cost: next_cost });
+        }
+    }
+
+    Ok(None)
+}
+
+fn set_best<S>(best: &mut Vec<Option<Node<S>>>, slot: usize, node: Node<S>) {
+    if slot >= best.len() {
+        best.resize_with(slot + 1, || None);
+    }
+    best[slot] = Some(node);
+}
+
+// All states reachable from the start (including the start itself), in depth-first order: the same order in which a
+// recursive flood fill would visit them.
+pub fn reachable<S, N, I>(start: S, mut neighbours: N) -> Vec<S>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>
+{
+    let mut result = vec![];
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if !seen.insert(state.clone()) {
+            continue;
+        }
+        // Reversed, so the first neighbour is visited first.
+        let mut next: Vec<S> = neighbours(&state).into_iter().filter(|n| !seen.contains(n)).collect();
+        next.reverse();
+        stack.extend(next);
+        result.push(state);
+    }
+
+    result
+}
+
+struct Node<S> {
+    state: S,
+    parent: Option<usize>,
+    cost: usize,
+}
+
+fn reconstruct<S>(mut nodes: Vec<Node<S>>, goal: usize) -> SearchResult<S> {
+    let cost = nodes[goal].cost;
+    let mut indices = vec![goal];
+    while let Some(parent) = nodes[*indices.last().unwrap()].parent {
+        indices.push(parent);
+    }
+
+    // Parents always come before their children, so the indices are descending, and removing them in this order never
+    // moves a node that is still needed.
+    let mut path: Vec<S> = indices.iter().map(|i| nodes.swap_remove(*i).state).collect();
+    path.reverse();
+    SearchResult { cost, path }
+}
+
+// The path to the goal from the cheapest ways found by A*, which refer to their parents by slot.
+fn reconstruct_best<S>(mut best: Vec<Option<Node<S>>>, goal: usize) -> SearchResult<S> {
+    let cost = best[goal].as_ref().map_or(0, |node| node.cost);
+    let mut path = vec![];
+    let mut next = Some(goal);
+    while let Some(node) = next.and_then(|slot| best[slot].take()) {
+        next = node.parent;
+        path.push(node.state);
+    }
+
+    path.reverse();
+    SearchResult { cost, path }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::error::Error;
+    use crate::util::cancel::{run_with, CancellationToken};
+    use crate::util::search::{astar, astar_indexed, bfs, dijkstra, dijkstra_indexed, reachable, SearchResult};
+
+    // A line of numbers, where going up by one costs 10 and jumping up by three costs 20.
+    fn successors(n: &i32) -> Vec<(i32, usize)> {
+        vec![(n + 1, 10), (n + 3, 20)]
+    }
+
+    #[test]
+    fn test_bfs() {
+        assert_eq!(bfs(0, successors, |n| *n == 6), Ok(Some(SearchResult { cost: 2, path: vec![0, 3, 6] })));
+        assert_eq!(bfs(0, |n| if *n < 5 { successors(n) } else { vec![] }, |n| *n == 10), Ok(None));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(dijkstra(0, successors, |n| *n == 6), Ok(Some(SearchResult { cost: 40, path: vec![0, 3, 6] })));
+        assert_eq!(dijkstra(0, successors, |n| *n == 0), Ok(Some(SearchResult { cost: 0, path: vec![0] })));
+
+        // The direct way is fewer steps, but more expensive.
+        let graph = |n: &char| match n {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+        assert_eq!(dijkstra('a', graph, |n| *n == 'd'), Ok(Some(SearchResult { cost: 3, path: vec!['a', 'b', 'c', 'd'] })));
+        assert_eq!(bfs('a', graph, |n| *n == 'd'), Ok(Some(SearchResult { cost: 1, path: vec!['a', 'd'] })));
+    }
+
+    #[test]
+    fn test_astar() {
+        let result = astar(0, successors, |n| (20 - n).max(0) as usize / 3 * 20, |n| *n == 20).unwrap();
+        assert_eq!(result.map(|r| r.cost), Some(140));
+        assert_eq!(astar(0, |n| if *n < 5 { successors(n) } else { vec![] }, |_| 0, |n| *n == 10), Ok(None));
+    }
+
+    #[test]
+    fn test_indexed() {
+        let index = |n: &i32| *n as usize;
+        assert_eq!(dijkstra_indexed(0, successors, |n| *n == 6, index, 10), Ok(Some(SearchResult { cost: 40, path: vec![0, 3, 6] })));
+        assert_eq!(dijkstra_indexed(0, successors, |n| *n == 0, index, 10), Ok(Some(SearchResult { cost: 0, path: vec![0] })));
+        // The capacity only needs to cover the states that are searched.
+        let bounded = |n: &i32| successors(n).into_iter().filter(|(n, _)| *n < 10).collect::<Vec<_>>();
+        assert_eq!(dijkstra_indexed(0, bounded, |n| *n == 12, index, 10), Ok(None));
+
+        let result = astar_indexed(0, successors, |n| (20 - n).max(0) as usize / 3 * 20, |n| *n == 20, index, 30).unwrap();
+        assert_eq!(result.map(|r| r.cost), Some(140));
+    }
+
+    #[test]
+    fn test_search_timeout() {
+        let result = run_with(Some(CancellationToken::with_timeout(Duration::ZERO)), || {
+            std::thread::sleep(Duration::from_millis(1));
+            dijkstra(0, successors, |_| false)
+        });
+        assert_eq!(result, Err(Error::TimedOut(Duration::ZERO)));
+    }
+
+    #[test]
+    fn test_reachable() {
+        assert_eq!(reachable(0, |n| if *n < 4 { vec![n + 1, n * 2] } else { vec![] }), vec![0, 1, 2, 3, 4, 6]);
+        assert_eq!(reachable(1, |n| if *n < 8 { vec![n * 2, n * 3] } else { vec![] }), vec![1, 2, 4, 8, 12, 6, 18, 3, 9]);
+        assert_eq!(reachable(0, |_| vec![]), vec![0]);
+    }
+}
@@ -0,0 +1,154 @@
+use advent_of_code_2021::get_day;
+use advent_of_code_2021::util::input::InputSource;
+
+const YEAR: i32 = 2021;
+
+// Runs both puzzles of the given day on resources/2021/examples/dayNN.txt, the same way the CLI runs them.
+fn assert_example(day: i32, expected1: &str, expected2: &str) {
+    assert_input(day, InputSource::Example(YEAR, day, None), expected1, expected2);
+}
+
+// Runs both puzzles of the given day on resources/2021/examples/dayNN-name.txt.
+fn assert_named_example(day: i32, name: &str, expected1: &str, expected2: &str) {
+    assert_input(day, InputSource::Example(YEAR, day, Some(name.to_string())), expected1, expected2);
+}
+
+fn assert_input(day: i32, source: InputSource, expected1: &str, expected2: &str) {
+    let input = source.read().unwrap_or_else(|e| panic!("{}", e));
+    let solver = get_day(YEAR, day).unwrap();
+    let parsed = (solver.parse)(&input).unwrap_or_else(|e| panic!("Day {} parse failed: {}", day, e));
+
+    let answers: Vec<String> = solver.puzzles(None).into_iter()
+        .map(|(puzzle, solve)| solve(&parsed).unwrap_or_else(|e| panic!("Day {} puzzle {} failed: {}", day, puzzle, e)).to_string())
+        .collect();
+    assert_eq!(answers, vec![expected1.to_string(), expected2.to_string()], "Day {}", day);
+}
+
+#[test]
+fn day01() {
+    assert_example(1, "7", "5");
+}
+
+#[test]
+fn day02() {
+    assert_example(2, "150", "900");
+}
+
+#[test]
+fn day03() {
+    assert_example(3, "198", "230");
+}
+
+#[test]
+fn day04() {
+    assert_example(4, "4512", "1924");
+}
+
+#[test]
+fn day05() {
+    assert_example(5, "5", "12");
+}
+
+#[test]
+fn day06() {
+    assert_example(6, "5934", "26984457539");
+}
+
+#[test]
+fn day07() {
+    assert_example(7, "37", "168");
+}
+
+#[test]
+fn day08() {
+    assert_example(8, "26", "61229");
+}
+
+#[test]
+fn day09() {
+    assert_example(9, "15", "1134");
+}
+
+#[test]
+fn day10() {
+    assert_example(10, "26397", "288957");
+}
+
+#[test]
+fn day11() {
+    assert_example(11, "1656", "195");
+}
+
+#[test]
+fn day12() {
+    assert_named_example(12, "small", "10", "36");
+    assert_named_example(12, "large", "19", "103");
+    assert_named_example(12, "huge", "226", "3509");
+}
+
+#[test]
+fn day13() {
+    assert_example(13, "17", "\
+        #####\n\
+        #...#\n\
+        #...#\n\
+        #...#\n\
+        #####");
+}
+
+#[test]
+fn day14() {
+    assert_example(14, "1588", "2188189693529");
+}
+
+#[test]
+fn day15() {
+    assert_example(15, "40", "315");
+}
+
+#[test]
+fn day16() {
+    assert_example(16, "20", "1");
+}
+
+#[test]
+fn day17() {
+    assert_example(17, "45", "112");
+}
+
+#[test]
+fn day18() {
+    assert_example(18, "4140", "3993");
+}
+
+#[test]
+fn day19() {
+    assert_example(19, "79", "3621");
+}
+
+#[test]
+fn day20() {
+    assert_example(20, "35", "3351");
+}
+
+#[test]
+fn day21() {
+    assert_example(21, "739785", "444356092776315");
+}
+
+#[test]
+fn day22() {
+    assert_example(22, "590784", "39769202357779");
+}
+
+#[test]
+fn day23() {
+    assert_example(23, "12521", "44169");
+}
+
+// Day 24 has no example input: the puzzle only makes sense for the MONAD program in the real input.
+
+#[test]
+fn day25() {
+    assert_example(25, "58", "Puzzle 2 is a freebie, as always :D");
+}